@@ -1,6 +1,7 @@
 use std::sync::LazyLock;
 
 use crate::api;
+use crate::error::Error;
 
 use indexmap::IndexMap;
 use serde::Deserialize;
@@ -63,13 +64,13 @@ static MCJARS_URL: LazyLock<String> =
     LazyLock::new(|| std::env::var("MCJARS_URL").unwrap_or("https://mcjars.app".to_string()));
 const MCJARS_FIELDS: &str = "id,type,versionId,projectVersionId,name,installation,changes";
 
-pub async fn lookup(file: &str) -> Result<([Build; 2], IndexMap<String, Version>), reqwest::Error> {
+pub async fn lookup(file: &str) -> Result<([Build; 2], IndexMap<String, Version>), Error> {
     let mut sha512 = sha2::Sha512::new();
-    let mut file = tokio::fs::File::open(file).await.unwrap();
+    let mut file = tokio::fs::File::open(file).await?;
 
     loop {
         let mut buffer = vec![0; 64 * 1024];
-        let count = file.read(&mut buffer).await.unwrap();
+        let count = file.read(&mut buffer).await?;
 
         if count == 0 {
             break;
@@ -98,7 +99,7 @@ pub async fn lookup(file: &str) -> Result<([Build; 2], IndexMap<String, Version>
         latest: Build,
     }
 
-    let versions = versions(&data.build.r#type).await.unwrap();
+    let versions = versions(&data.build.r#type).await?;
     Ok(([data.build, data.latest], versions))
 }
 
@@ -120,7 +121,7 @@ pub async fn lookup_id(id: u32) -> Result<(Build, IndexMap<String, Version>), re
         build: Build,
     }
 
-    let versions = versions(&data.build.r#type).await.unwrap();
+    let versions = versions(&data.build.r#type).await?;
     Ok((data.build, versions))
 }
 
@@ -1,4 +1,5 @@
 use crate::api;
+use crate::error::Error;
 
 use indexmap::IndexMap;
 use serde::Deserialize;
@@ -15,6 +16,7 @@ use tokio::io::AsyncReadExt;
 pub struct Project {
     pub id: Option<String>,
     pub project_id: Option<String>,
+    pub slug: Option<String>,
     pub title: String,
     pub description: String,
     pub downloads: u32,
@@ -94,7 +96,7 @@ pub async fn lookup(
     folder: &str,
     loader: Option<&str>,
     version: Option<&str>,
-) -> Result<IndexMap<PathBuf, Project>, Box<dyn std::error::Error>> {
+) -> Result<IndexMap<PathBuf, Project>, Error> {
     let mut read_dir = tokio::fs::read_dir(folder).await?;
     let mut hashes = HashMap::new();
 
@@ -0,0 +1,83 @@
+use flate2::{Compression, write::GzEncoder};
+use std::{fs::File, path::PathBuf};
+
+pub const DIRECTORY: &str = ".mcvcli/backups";
+const TIMESTAMP: &str = "%Y%m%d-%H%M%S";
+
+pub fn list() -> Vec<PathBuf> {
+    let mut backups = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(DIRECTORY) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.is_file() && path.to_string_lossy().ends_with(".tar.gz") {
+                backups.push(path);
+            }
+        }
+    }
+
+    backups.sort();
+
+    backups
+}
+
+/// Archives the server directory (without `.mcvcli*` entries) into
+/// `.mcvcli/backups/{name}-{timestamp}.tar.gz`, keeping only the newest `keep` backups with that name.
+pub fn create(name: &str, keep: Option<usize>) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(DIRECTORY)?;
+
+    let path = PathBuf::from(DIRECTORY).join(format!(
+        "{}-{}.tar.gz",
+        name,
+        chrono::Local::now().format(TIMESTAMP)
+    ));
+
+    let file = File::create(&path)?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    archive.follow_symlinks(false);
+
+    for entry in std::fs::read_dir(".")?.flatten() {
+        let file_name = entry.file_name();
+
+        if file_name.to_string_lossy().starts_with(".mcvcli") {
+            continue;
+        }
+
+        if entry.path().is_dir() {
+            archive.append_dir_all(&file_name, entry.path())?;
+        } else {
+            archive.append_path_with_name(entry.path(), &file_name)?;
+        }
+    }
+
+    archive.into_inner()?.finish()?.sync_all()?;
+
+    if let Some(keep) = keep {
+        // only `{name}-{timestamp}.tar.gz`, `{name}-other-{timestamp}.tar.gz` belongs to
+        // another schedule
+        let backups = list()
+            .into_iter()
+            .filter(|backup| {
+                backup.file_name().is_some_and(|file_name| {
+                    file_name
+                        .to_string_lossy()
+                        .strip_prefix(name)
+                        .and_then(|rest| rest.strip_prefix('-'))
+                        .and_then(|rest| rest.strip_suffix(".tar.gz"))
+                        .is_some_and(|timestamp| {
+                            chrono::NaiveDateTime::parse_from_str(timestamp, TIMESTAMP).is_ok()
+                        })
+                })
+            })
+            .collect::<Vec<PathBuf>>();
+
+        if backups.len() > keep {
+            for backup in &backups[..backups.len() - keep] {
+                std::fs::remove_file(backup).unwrap_or_default();
+            }
+        }
+    }
+
+    Ok(path)
+}
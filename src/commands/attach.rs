@@ -1,25 +1,37 @@
+use crate::error::{Error, Result};
+use crate::supervisor::{
+    client::Client,
+    protocol::{self, Event, Request, Response},
+};
 use crate::{config, detached};
 
 use clap::ArgMatches;
 use colored::Colorize;
+use tokio::io::{AsyncBufReadExt, BufReader};
 
-pub async fn attach(_matches: &ArgMatches) -> i32 {
-    let mut config = config::Config::new(".mcvcli.json", false);
+pub async fn attach(matches: &ArgMatches) -> Result {
+    let tail = *matches.get_one::<usize>("tail").expect("required");
+    let config = config::Config::new(".mcvcli.json", false)?;
 
-    if !detached::status(config.pid) {
-        println!(
-            "{} {}",
-            "server is not running, use".red(),
-            "mcvcli start --detach".cyan()
-        );
-        return 1;
+    if !detached::running(&config) {
+        return Err(Error::not_running());
     }
 
     println!("{}", "attaching to server ...".bright_black());
 
-    let [mut stdin, mut stdout, mut stderr] =
-        detached::get_pipes(&config.identifier.clone().unwrap());
-    let mut threads = Vec::with_capacity(3);
+    let mut client = match Client::connect(config.identifier.as_deref().unwrap_or_default()).await {
+        Ok(client) => client,
+        Err(_) => {
+            println!(
+                "{} {}",
+                "attaching to server ...".bright_black(),
+                "FAILED".red().bold()
+            );
+            return Err(Error::io("unable to connect to the server supervisor"));
+        }
+    };
+
+    client.send(&Request::Subscribe { tail }).await?;
 
     println!(
         "{} {}",
@@ -28,40 +40,55 @@ pub async fn attach(_matches: &ArgMatches) -> i32 {
     );
     println!();
 
-    threads.push(tokio::spawn(async move {
-        std::io::copy(&mut std::io::stdin(), &mut stdin).unwrap();
-    }));
+    let Client {
+        mut reader,
+        mut writer,
+    } = client;
 
-    threads.push(tokio::spawn(async move {
-        std::io::copy(&mut stdout, &mut std::io::stdout().lock()).unwrap();
-    }));
-
-    threads.push(tokio::spawn(async move {
-        std::io::copy(&mut stderr, &mut std::io::stderr().lock()).unwrap();
-    }));
-
-    tokio::spawn(async move {
-        loop {
-            if !detached::status(config.pid) {
-                println!();
-                println!("{}", "server has stopped".red());
-
-                config.pid = None;
-                config.identifier = None;
-                config.save();
+    let mut input = tokio::spawn(async move {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
 
+        while let Ok(Some(command)) = lines.next_line().await {
+            if protocol::write(&mut writer, &Request::Command { command })
+                .await
+                .is_err()
+            {
                 break;
             }
+        }
+    });
 
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    let mut output = tokio::spawn(async move {
+        loop {
+            match protocol::read(&mut reader).await {
+                Ok(Some(Response::Event {
+                    event: Event::Line { line },
+                })) => println!("{line}"),
+                Ok(Some(Response::Event {
+                    event: Event::Exited { .. },
+                })) => break,
+                Ok(Some(Response::Error { message })) => println!("{}", message.red()),
+                Ok(Some(_)) => continue,
+                Ok(None) | Err(_) => break,
+            }
         }
-    })
-    .await
-    .unwrap();
+    });
+
+    tokio::select! {
+        biased;
 
-    for thread in threads {
-        thread.abort();
+        _ = &mut input => {
+            println!();
+            println!("{}", "detached from server".bright_black());
+        }
+        _ = &mut output => {
+            println!();
+            println!("{}", "server has stopped".red());
+        }
     }
 
-    0
+    input.abort();
+    output.abort();
+
+    Ok(())
 }
@@ -0,0 +1,85 @@
+pub mod validate;
+
+use crate::error::{Error, Result};
+use crate::{config, profiles};
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn config(matches: &ArgMatches) -> Result {
+    let profile = matches.get_one::<String>("profile");
+
+    if let Some(profile) = profile
+        && !profiles::list().contains(profile)
+    {
+        return Err(Error::not_found(format!(
+            "profile `{profile}` does not exist"
+        )));
+    }
+
+    let mut config = if let Some(profile) = profile {
+        config::Config::new(&format!(".mcvcli.profiles/{profile}/.mcvcli.json"), false)?
+    } else {
+        config::Config::new(".mcvcli.json", false)?
+    };
+
+    let ram = matches.get_one::<u32>("ram");
+    let stop_command = matches.get_one::<String>("stop_command");
+    let stop_transport = matches.get_one::<String>("stop_transport");
+    let flags = matches.get_one::<String>("flags");
+    let args = matches.get_one::<String>("args");
+    let restart = matches.get_one::<String>("restart");
+    let watchdog = matches.get_one::<String>("watchdog");
+
+    if ram.is_none()
+        && stop_command.is_none()
+        && stop_transport.is_none()
+        && flags.is_none()
+        && args.is_none()
+        && restart.is_none()
+        && watchdog.is_none()
+    {
+        return Err(Error::other("no changes made, use `mcvcli config --help`"));
+    }
+
+    println!("{}", "updating config ...".bright_black());
+
+    if let Some(ram) = ram {
+        config.ram_mb = *ram;
+    }
+    if let Some(stop_command) = stop_command {
+        config.stop_command = stop_command.to_string();
+    }
+    if let Some(stop_transport) = stop_transport {
+        config.stop_transport = match stop_transport.as_str() {
+            "rcon" => config::StopTransport::Rcon,
+            _ => config::StopTransport::Console,
+        };
+    }
+    if let Some(flags) = flags {
+        config.extra_flags = vec![flags.to_string()];
+    }
+    if let Some(args) = args {
+        config.extra_args = vec![args.to_string()];
+    }
+    if let Some(restart) = restart {
+        config.restart.policy = match restart.as_str() {
+            "on-failure" => config::RestartPolicy::OnFailure,
+            "always" => config::RestartPolicy::Always,
+            _ => config::RestartPolicy::Never,
+        };
+    }
+    if let Some(watchdog) = watchdog {
+        config.watchdog.enabled = watchdog == "on";
+    }
+
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "updating config ...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    Ok(())
+}
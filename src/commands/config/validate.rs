@@ -0,0 +1,174 @@
+use crate::error::{Error, Result};
+use crate::{config, java, profiles};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use serde_json::Value;
+use std::path::Path;
+
+/// Collects the keys of `value` that do not exist in `known`, the config serialized again.
+fn unknown_keys(value: &Value, known: &Value, prefix: &str, keys: &mut Vec<String>) {
+    match (value, known) {
+        (Value::Object(fields), Value::Object(known)) => {
+            for (key, value) in fields {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+
+                match known.get(key) {
+                    Some(known) => unknown_keys(value, known, &path, keys),
+                    None => keys.push(path),
+                }
+            }
+        }
+        (Value::Array(values), Value::Array(known)) => {
+            for (i, (value, known)) in values.iter().zip(known).enumerate() {
+                unknown_keys(value, known, &format!("{prefix}[{i}]"), keys);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub async fn validate(matches: &ArgMatches) -> Result {
+    let profile = matches.get_one::<String>("profile");
+
+    if let Some(profile) = profile
+        && !profiles::list().contains(profile)
+    {
+        return Err(Error::not_found(format!(
+            "profile `{profile}` does not exist"
+        )));
+    }
+
+    let path = match profile {
+        Some(profile) => format!(".mcvcli.profiles/{profile}/.mcvcli.json"),
+        None => ".mcvcli.json".to_string(),
+    };
+
+    println!("{}", "validating config ...".bright_black());
+
+    let config = config::Config::new(&path, false)?;
+    let value: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)
+        .map_err(|err| Error::InvalidConfig(format!("unable to parse `{path}`: {err}")))?;
+
+    let mut problems = Vec::new();
+
+    let mut keys = Vec::new();
+    unknown_keys(
+        &value,
+        &serde_json::to_value(&config).unwrap(),
+        "",
+        &mut keys,
+    );
+    for key in keys {
+        problems.push((key, "unknown key".to_string()));
+    }
+
+    if !(config::MIN_RAM_MB..=config::MAX_RAM_MB).contains(&config.ram_mb) {
+        problems.push((
+            "ramMB".to_string(),
+            format!(
+                "{} is out of range, expected {} to {}",
+                config.ram_mb,
+                config::MIN_RAM_MB,
+                config::MAX_RAM_MB
+            ),
+        ));
+    }
+
+    let directory = Path::new(&path).parent().unwrap_or(Path::new("."));
+    if !directory.join(&config.jar_file).is_file() {
+        problems.push((
+            "jarFile".to_string(),
+            format!("{} does not exist", config.jar_file),
+        ));
+    }
+
+    match java::versions().await {
+        Ok(versions) => {
+            if !versions.contains(&config.java_version) {
+                problems.push((
+                    "javaVersion".to_string(),
+                    format!("java {} is not provided by adoptium", config.java_version),
+                ));
+            }
+        }
+        Err(err) => {
+            println!(
+                "{} {}",
+                "unable to check javaVersion against adoptium:".yellow(),
+                err.to_string().yellow()
+            );
+        }
+    }
+
+    if problems.is_empty() {
+        println!(
+            "{} {}",
+            "validating config ...".bright_black(),
+            "DONE".green().bold()
+        );
+
+        return Ok(());
+    }
+
+    println!(
+        "{} {}",
+        "validating config ...".bright_black(),
+        "FAILED".red().bold()
+    );
+
+    for (key, problem) in problems.iter() {
+        println!("  {} {}", key.cyan(), problem.red());
+    }
+
+    Err(Error::InvalidConfig(format!(
+        "found {} problem(s) in `{path}`",
+        problems.len()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn keys(value: Value, known: Value) -> Vec<String> {
+        let mut keys = Vec::new();
+        unknown_keys(&value, &known, "", &mut keys);
+
+        keys
+    }
+
+    #[test]
+    fn reports_unknown_keys() {
+        assert_eq!(
+            keys(
+                json!({ "ramMB": 2048, "ramMb": 1024, "restart": { "policy": "never", "polcy": 1 } }),
+                json!({ "ramMB": 2048, "restart": { "policy": "never" } }),
+            ),
+            ["ramMb", "restart.polcy"]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_keys_in_arrays() {
+        assert_eq!(
+            keys(
+                json!({ "schedule": [{ "name": "a" }, { "name": "b", "cron": "x" }] }),
+                json!({ "schedule": [{ "name": "a" }, { "name": "b" }] }),
+            ),
+            ["schedule[1].cron"]
+        );
+    }
+
+    #[test]
+    fn accepts_known_keys() {
+        let value = json!({ "ramMB": 2048, "env": { "TZ": "UTC" } });
+
+        assert!(keys(value.clone(), value).is_empty());
+    }
+}
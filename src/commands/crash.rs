@@ -0,0 +1,192 @@
+use crate::error::{Error, Result};
+use crate::{api, crash};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+pub async fn crash(matches: &ArgMatches) -> Result {
+    let report = matches.get_one::<String>("report").expect("required");
+
+    let path = if report == "latest" {
+        match crash::latest() {
+            Some(path) => path,
+            None => {
+                return Err(Error::not_found(format!(
+                    "no crash reports found in `{}` directory",
+                    crash::DIRECTORY
+                )));
+            }
+        }
+    } else if Path::new(report).exists() {
+        PathBuf::from(report)
+    } else {
+        Path::new(crash::DIRECTORY).join(report)
+    };
+
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(Error::not_found(format!(
+                "crash report `{}` does not exist",
+                path.display()
+            )));
+        }
+        Err(err) => {
+            return Err(Error::io(format!(
+                "unable to read `{}`: {err}",
+                path.display()
+            )));
+        }
+    };
+
+    println!(
+        "{} {} {}",
+        "analyzing crash report".bright_black(),
+        path.display().to_string().cyan(),
+        "...".bright_black()
+    );
+
+    let report = crash::parse(&content);
+    let suspects = tokio::task::spawn_blocking(|| crash::packages(Path::new("mods")))
+        .await
+        .map(|packages| crash::suspects(&report, &packages))
+        .unwrap_or_default();
+
+    let projects = if suspects.is_empty() {
+        Default::default()
+    } else {
+        api::modrinth::lookup("mods", None, None)
+            .await
+            .unwrap_or_default()
+    };
+
+    println!(
+        "{} {} {} {}",
+        "analyzing crash report".bright_black(),
+        path.display().to_string().cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+    println!();
+
+    println!(
+        "{}",
+        path.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .cyan()
+            .bold()
+            .underline()
+    );
+
+    if let Some(time) = &report.time {
+        println!("  {} {}", "time:       ".bright_black(), time.cyan());
+    }
+    if let Some(description) = &report.description {
+        println!("  {} {}", "description:".bright_black(), description.cyan());
+    }
+
+    for (i, exception) in report.exceptions.iter().enumerate() {
+        println!(
+            "  {} {}{}",
+            if i == 0 {
+                "exception:  ".bright_black()
+            } else {
+                "caused by:  ".bright_black()
+            },
+            exception.class.cyan(),
+            exception
+                .message
+                .as_ref()
+                .map(|message| format!(": {message}"))
+                .unwrap_or_default()
+        );
+    }
+
+    if let Some(root) = report.exceptions.last() {
+        println!("  {}", "root cause frames:".bright_black());
+
+        for frame in root.frames.iter().take(5) {
+            println!(
+                "    {}",
+                format!("{}.{}({})", frame.class, frame.method, frame.source).bright_black()
+            );
+        }
+        if root.frames.len() > 5 {
+            println!(
+                "    {}",
+                format!("... {} more", root.frames.len() - 5).bright_black()
+            );
+        }
+    }
+
+    if !report.mods.is_empty() {
+        println!(
+            "  {} {}",
+            "mods loaded:".bright_black(),
+            report.mods.len().to_string().cyan()
+        );
+    }
+
+    println!();
+
+    if suspects.is_empty() {
+        println!(
+            "{}",
+            "no mod in the mods folder shows up in the stack trace".bright_black()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "suspected mods:".yellow().bold());
+
+    for suspect in suspects.iter() {
+        let project = projects.get(&suspect.jar);
+        let file_name = suspect
+            .jar
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        println!();
+        match project {
+            Some(project) => println!(
+                "{} {}",
+                project.title.cyan().bold().underline(),
+                format!("({file_name})").bright_black()
+            ),
+            None => println!("{}", file_name.cyan().bold().underline()),
+        }
+
+        println!(
+            "  {} {} {}",
+            "frames:".bright_black(),
+            suspect.frames.to_string().cyan(),
+            format!(
+                "(first at {}.{})",
+                suspect.first.class, suspect.first.method
+            )
+            .bright_black()
+        );
+
+        if let Some(project) = project {
+            println!(
+                "  {} {}",
+                "link:  ".bright_black(),
+                format!(
+                    "https://modrinth.com/mod/{}",
+                    project
+                        .slug
+                        .as_ref()
+                        .or(project.id.as_ref())
+                        .unwrap_or(&project.title)
+                )
+                .cyan()
+            );
+        }
+    }
+
+    Ok(())
+}
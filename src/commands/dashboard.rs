@@ -0,0 +1,24 @@
+use crate::dashboard::{self, Mode};
+use crate::error::{Error, Result};
+use crate::{config, detached};
+
+use clap::ArgMatches;
+
+pub async fn dashboard(_matches: &ArgMatches) -> Result {
+    let config = config::Config::new(".mcvcli.json", false)?;
+
+    if !detached::running(&config) {
+        return Err(Error::NotRunning(
+            "server is not running, use `mcvcli start --detached` or `mcvcli start --dashboard`"
+                .to_string(),
+        ));
+    }
+
+    dashboard::run(
+        &config,
+        config.identifier.as_deref().unwrap_or_default(),
+        Mode::Detached,
+    )
+    .await
+    .map_err(|err| Error::io(format!("unable to open the dashboard: {err}")))
+}
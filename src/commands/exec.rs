@@ -0,0 +1,77 @@
+use crate::error::{Error, Result};
+use crate::supervisor::client::Client;
+use crate::{config, detached};
+
+use clap::ArgMatches;
+use regex::Regex;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Serialize)]
+struct Output<'a> {
+    command: &'a str,
+    lines: Vec<String>,
+    matched: Option<bool>,
+    exited: bool,
+}
+
+pub async fn exec(matches: &ArgMatches) -> Result {
+    let command = matches
+        .get_many::<String>("command")
+        .expect("required")
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(" ");
+    let timeout = *matches.get_one::<Duration>("timeout").expect("required");
+    let until = matches.get_one::<String>("until");
+    let json = *matches.get_one::<bool>("json").expect("required");
+
+    let until = match until.map(|until| Regex::new(until)) {
+        Some(Ok(until)) => Some(until),
+        Some(Err(err)) => return Err(Error::other(format!("invalid regex: {err}"))),
+        None => None,
+    };
+
+    let config = config::Config::new(".mcvcli.json", false)?;
+
+    if !detached::running(&config) {
+        return Err(Error::not_running());
+    }
+
+    let mut client = match Client::connect(config.identifier.as_deref().unwrap_or_default()).await {
+        Ok(client) => client,
+        Err(_) => return Err(Error::io("unable to connect to the server supervisor")),
+    };
+
+    let capture = match client.capture(&command, timeout, until.as_ref()).await {
+        Ok(capture) => capture,
+        Err(err) => {
+            return Err(Error::io(err));
+        }
+    };
+
+    let timed_out = until.is_some() && !capture.matched;
+
+    if json {
+        let output = Output {
+            command: &command,
+            matched: until.as_ref().map(|_| capture.matched),
+            exited: capture.exited,
+            lines: capture.lines,
+        };
+
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+    } else {
+        for line in capture.lines {
+            println!("{line}");
+        }
+    }
+
+    if timed_out {
+        return Err(Error::TimedOut(
+            "timed out waiting for a matching line".to_string(),
+        ));
+    }
+
+    Ok(())
+}
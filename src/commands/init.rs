@@ -1,4 +1,5 @@
-use crate::{api, config, jar, java, modpack};
+use crate::error::{Error, Result};
+use crate::{api, config, jar, java, modpack, registry};
 
 use clap::ArgMatches;
 use colored::Colorize;
@@ -8,33 +9,26 @@ pub async fn init(
     matches: &ArgMatches,
     override_directory: Option<&str>,
     profile_name: Option<&str>,
-) -> i32 {
+) -> Result {
     let directory = override_directory
         .or_else(|| matches.get_one::<String>("directory").map(|d| d.as_str()))
         .unwrap();
 
     if std::path::Path::new(&format!("{directory}/.mcvcli.json")).exists() {
-        println!("{} {}", ".mcvcli.json".cyan(), "already exists!".red());
-        println!(
-            "{} {} {}",
-            "use".red(),
-            "mcvcli install".cyan(),
-            "instead.".red()
-        );
-
-        return 1;
+        return Err(Error::other(
+            "`.mcvcli.json` already exists, use `mcvcli install` instead",
+        ));
     }
 
-    std::fs::create_dir_all(directory).unwrap();
+    std::fs::create_dir_all(directory)?;
 
-    let jars = std::fs::read_dir(directory)
-        .unwrap()
+    let jars = std::fs::read_dir(directory)?
+        .flatten()
         .filter_map(|entry| {
-            let entry = entry.unwrap();
             let path = entry.path();
 
             if path.is_file() {
-                let name = path.file_name().unwrap().to_str().unwrap();
+                let name = path.file_name()?.to_string_lossy();
 
                 if name.ends_with(".jar") {
                     return Some(name.to_string());
@@ -56,8 +50,7 @@ pub async fn init(
                 let index = jars.iter().position(|x| x == &jar).unwrap();
                 index + 2
             } else {
-                println!("{} {}", jar.cyan(), "not found!".red());
-                return 1;
+                return Err(Error::not_found(format!("`{jar}` not found")));
             }
         }
     } else {
@@ -67,8 +60,7 @@ pub async fn init(
             .item("Install New (Jar)")
             .item("Install New (Modrinth Modpack)")
             .items(&jars)
-            .interact()
-            .unwrap()
+            .interact()?
     };
 
     match server_jarfile {
@@ -102,7 +94,7 @@ pub async fn init(
                         "...".bright_black()
                     );
 
-                    jar::install(&server_build, directory, 1).await.unwrap();
+                    jar::install(&server_build, directory, 1).await?;
 
                     println!(
                         "{} {} {} {} {}",
@@ -118,18 +110,14 @@ pub async fn init(
                         .unwrap_or(versions.last().unwrap().1)
                         .java
                 } else {
-                    println!(
-                        "{} {} {}",
-                        "server build".red(),
-                        build_id.to_string().cyan(),
-                        "not found!".red()
-                    );
-                    return 1;
+                    return Err(Error::not_found(format!(
+                        "server build `{build_id}` not found"
+                    )));
                 }
             } else {
                 println!("{}", "getting server types...".bright_black());
 
-                let types = api::mcjars::types().await.unwrap();
+                let types = api::mcjars::types().await?;
 
                 println!(
                     "{} {}",
@@ -139,13 +127,7 @@ pub async fn init(
 
                 let server_type = if let Some(r#type) = matches.get_one::<String>("type") {
                     if !types.contains_key(&r#type.to_uppercase()) {
-                        println!(
-                            "{} {} {}",
-                            "server type".red(),
-                            r#type.to_string().cyan(),
-                            "not found!".red()
-                        );
-                        return 1;
+                        return Err(Error::not_found(format!("server type `{type}` not found")));
                     }
 
                     &r#type.to_uppercase()
@@ -155,8 +137,7 @@ pub async fn init(
                         .default(0)
                         .items(&types.values().map(|t| &t.name).collect::<Vec<&String>>())
                         .max_length(10)
-                        .interact()
-                        .unwrap();
+                        .interact()?;
 
                     types.keys().nth(server_type).unwrap()
                 };
@@ -168,7 +149,7 @@ pub async fn init(
                     "...".bright_black()
                 );
 
-                let versions = api::mcjars::versions(server_type).await.unwrap();
+                let versions = api::mcjars::versions(server_type).await?;
 
                 println!(
                     "{} {} {} {}",
@@ -180,13 +161,9 @@ pub async fn init(
 
                 let server_version = if let Some(version) = matches.get_one::<String>("version") {
                     if !versions.contains_key(version) {
-                        println!(
-                            "{} {} {}",
-                            "server version".red(),
-                            version.to_string().cyan(),
-                            "not found!".red()
-                        );
-                        return 1;
+                        return Err(Error::not_found(format!(
+                            "server version `{version}` not found"
+                        )));
                     }
 
                     version
@@ -196,8 +173,7 @@ pub async fn init(
                         .default(0)
                         .items(&versions.keys().rev().collect::<Vec<&String>>())
                         .max_length(10)
-                        .interact()
-                        .unwrap();
+                        .interact()?;
 
                     versions.keys().rev().nth(server_version).unwrap()
                 };
@@ -209,9 +185,7 @@ pub async fn init(
                     "...".bright_black()
                 );
 
-                let builds = api::mcjars::builds(server_type, server_version)
-                    .await
-                    .unwrap();
+                let builds = api::mcjars::builds(server_type, server_version).await?;
 
                 println!(
                     "{} {} {} {}",
@@ -222,18 +196,16 @@ pub async fn init(
                 );
 
                 let server_build = if let Some(build) = matches.get_one::<String>("build") {
-                    if build.as_str() == "latest" {
-                        builds.first().unwrap()
+                    if build.as_str() == "latest"
+                        && let Some(build) = builds.first()
+                    {
+                        build
                     } else if let Some(build) = builds.iter().find(|b| &b.name == build) {
                         build
                     } else {
-                        println!(
-                            "{} {} {}",
-                            "server build".red(),
-                            build.to_string().cyan(),
-                            "not found!".red()
-                        );
-                        return 1;
+                        return Err(Error::not_found(format!(
+                            "server build `{build}` not found"
+                        )));
                     }
                 } else {
                     let server_build = FuzzySelect::with_theme(&ColorfulTheme::default())
@@ -241,8 +213,7 @@ pub async fn init(
                         .default(0)
                         .items(&builds.iter().map(|b| &b.name).collect::<Vec<&String>>())
                         .max_length(10)
-                        .interact()
-                        .unwrap();
+                        .interact()?;
 
                     &builds[server_build]
                 };
@@ -255,7 +226,7 @@ pub async fn init(
                     "...".bright_black()
                 );
 
-                jar::install(server_build, directory, 1).await.unwrap();
+                jar::install(server_build, directory, 1).await?;
 
                 println!(
                     "{} {} {} {} {}",
@@ -275,19 +246,12 @@ pub async fn init(
                 Input::<u32>::with_theme(&ColorfulTheme::default())
                     .with_prompt("RAM (MB)")
                     .default(2048)
-                    .interact()
-                    .unwrap()
+                    .interact()?
             };
 
             let java = if let Some(java) = matches.get_one::<u8>("java") {
-                if !java::versions().await.contains(java) {
-                    println!(
-                        "{} {} {}",
-                        "java version".red(),
-                        java.to_string().cyan(),
-                        "not found!".red()
-                    );
-                    return 1;
+                if !java::versions().await?.contains(java) {
+                    return Err(Error::not_found(format!("java version `{java}` not found")));
                 }
 
                 *java
@@ -295,19 +259,18 @@ pub async fn init(
                 java
             };
 
-            let mut config = config::Config::new(&format!("{directory}/.mcvcli.json"), true);
+            let mut config = config::Config::new(&format!("{directory}/.mcvcli.json"), true)?;
             config.profile_name = profile_name.unwrap_or("default").to_string();
             config.java_version = java;
             config.ram_mb = ram_mb;
-            config.save();
+            config.save()?;
         }
         1 => {
             let mut projects = api::modrinth::projects(
                 "",
                 "[[\"project_type:modpack\"],[\"server_side != unsupported\"]]",
             )
-            .await
-            .unwrap();
+            .await?;
             let mut project;
 
             loop {
@@ -332,23 +295,18 @@ pub async fn init(
                             .collect::<Vec<String>>(),
                     )
                     .max_length(10)
-                    .interact()
-                    .unwrap();
+                    .interact()?;
 
                 project = modpack;
 
                 if modpack == 0 {
-                    let search = Input::<String>::new()
-                        .with_prompt("Search")
-                        .interact()
-                        .unwrap();
+                    let search = Input::<String>::new().with_prompt("Search").interact()?;
 
                     projects = api::modrinth::projects(
                         &search,
                         "[[\"project_type:modpack\"],[\"server_side != unsupported\"]]",
                     )
-                    .await
-                    .unwrap();
+                    .await?;
                 } else {
                     break;
                 }
@@ -364,9 +322,7 @@ pub async fn init(
                 "...".bright_black()
             );
 
-            let versions = api::modrinth::versions(project.project_id.as_ref().unwrap())
-                .await
-                .unwrap();
+            let versions = api::modrinth::versions(project.project_id.as_ref().unwrap()).await?;
             let versions = versions
                 .iter()
                 .filter(|v| !v.files.is_empty())
@@ -418,8 +374,7 @@ pub async fn init(
                         .collect::<Vec<String>>(),
                 )
                 .max_length(5)
-                .interact()
-                .unwrap();
+                .interact()?;
 
             let modpack_version = &versions[modpack_version];
 
@@ -429,8 +384,7 @@ pub async fn init(
                 Input::<u32>::with_theme(&ColorfulTheme::default())
                     .with_prompt("RAM (MB)")
                     .default(2048)
-                    .interact()
-                    .unwrap()
+                    .interact()?
             };
 
             println!();
@@ -441,9 +395,9 @@ pub async fn init(
                 "...".bright_black()
             );
 
-            modpack::install(directory, modpack_version).await;
+            modpack::install(directory, modpack_version).await?;
 
-            let mut config = config::Config::new(&format!("{directory}/.mcvcli.json"), true);
+            let mut config = config::Config::new(&format!("{directory}/.mcvcli.json"), true)?;
             config.profile_name = profile_name.unwrap_or("default").to_string();
             config.ram_mb = ram_mb;
             config.jar_file = "server.jar".to_string();
@@ -451,14 +405,8 @@ pub async fn init(
             config.modpack_version = Some(modpack_version.id.clone());
 
             if let Some(java) = matches.get_one::<u8>("java") {
-                if !java::versions().await.contains(java) {
-                    println!(
-                        "{} {} {}",
-                        "java version".red(),
-                        java.to_string().cyan(),
-                        "not found!".red()
-                    );
-                    return 1;
+                if !java::versions().await?.contains(java) {
+                    return Err(Error::not_found(format!("java version `{java}` not found")));
                 }
 
                 config.java_version = *java;
@@ -478,7 +426,7 @@ pub async fn init(
                 }
             }
 
-            config.save();
+            config.save()?;
 
             println!(
                 "{} {} {} {}",
@@ -493,7 +441,7 @@ pub async fn init(
 
             println!("{}", "getting java versions...".bright_black());
 
-            let java_versions = java::versions().await;
+            let java_versions = java::versions().await?;
 
             println!(
                 "{} {}",
@@ -503,13 +451,7 @@ pub async fn init(
 
             let java_version = if let Some(java) = matches.get_one::<u8>("java") {
                 if !java_versions.contains(java) {
-                    println!(
-                        "{} {} {}",
-                        "java version".red(),
-                        java.to_string().cyan(),
-                        "not found!".red()
-                    );
-                    return 1;
+                    return Err(Error::not_found(format!("java version `{java}` not found")));
                 }
 
                 *java
@@ -525,8 +467,7 @@ pub async fn init(
                             .collect::<Vec<String>>(),
                     )
                     .max_length(10)
-                    .interact()
-                    .unwrap();
+                    .interact()?;
 
                 *java_versions.iter().rev().nth(java_version).unwrap()
             };
@@ -537,18 +478,23 @@ pub async fn init(
                 Input::<u32>::with_theme(&ColorfulTheme::default())
                     .with_prompt("RAM (MB)")
                     .default(2048)
-                    .interact()
-                    .unwrap()
+                    .interact()?
             };
 
-            let mut config = config::Config::new(&format!("{directory}/.mcvcli.json"), true);
+            let mut config = config::Config::new(&format!("{directory}/.mcvcli.json"), true)?;
             config.profile_name = profile_name.unwrap_or("default").to_string();
             config.jar_file = jar_file.to_string();
             config.java_version = java_version;
             config.ram_mb = ram_mb;
-            config.save();
+            config.save()?;
         }
     }
 
-    0
+    if profile_name.is_none()
+        && let Some(name) = registry::register(std::path::Path::new(directory))
+    {
+        println!("{} {}", "registered server as".bright_black(), name.cyan());
+    }
+
+    Ok(())
 }
@@ -1,30 +1,28 @@
+use crate::error::{Error, Result};
+use crate::hooks::{self, Hook};
 use crate::{api, config, detached, jar, modpack};
 
 use clap::ArgMatches;
 use colored::Colorize;
 use dialoguer::{FuzzySelect, Input, Select, theme::ColorfulTheme};
 
-pub async fn install(matches: &ArgMatches) -> i32 {
-    let mut config = config::Config::new(".mcvcli.json", false);
+pub async fn install(matches: &ArgMatches) -> Result {
+    let mut config = config::Config::new(".mcvcli.json", false)?;
     let wipe = matches.get_one::<bool>("wipe").expect("required");
 
-    if detached::status(config.pid) {
-        println!(
-            "{} {}",
-            "server is currently running, use".red(),
-            "mcvcli stop".cyan()
-        );
-        return 1;
+    if detached::running(&config) {
+        return Err(Error::running());
+    }
+
+    if let Err(err) = hooks::run(Hook::PreInstall, &config, &[]).await {
+        return Err(Error::Other(err));
     }
 
     let server_jarfile = if let Some(file) = matches.get_one::<String>("file") {
         match file.as_str() {
             "install" => 0,
             "modrinth" => 1,
-            _ => {
-                println!("{} {}", file.cyan(), "not found".red());
-                return 1;
-            }
+            _ => return Err(Error::not_found(format!("`{file}` not found"))),
         }
     } else {
         Select::with_theme(&ColorfulTheme::default())
@@ -32,8 +30,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
             .default(0)
             .item("Install New (Jar)")
             .item("Install New (Modrinth Modpack)")
-            .interact()
-            .unwrap()
+            .interact()?
     };
 
     match server_jarfile {
@@ -41,26 +38,20 @@ pub async fn install(matches: &ArgMatches) -> i32 {
             if *wipe {
                 println!("{}", "Wiping server directory...".bright_black());
 
-                let entries = std::fs::read_dir(".").unwrap();
-                for entry in entries {
-                    let entry = entry.unwrap();
-                    let path = entry.path();
+                for entry in std::fs::read_dir(".")? {
+                    let path = entry?.path();
 
                     if path
                         .file_name()
-                        .unwrap()
-                        .to_str()
-                        .unwrap()
-                        .to_string()
-                        .starts_with(".mcvcli")
+                        .is_some_and(|name| name.to_string_lossy().starts_with(".mcvcli"))
                     {
                         continue;
                     }
 
                     if path.is_dir() {
-                        std::fs::remove_dir_all(&path).unwrap();
+                        std::fs::remove_dir_all(&path)?;
                     } else {
-                        std::fs::remove_file(&path).unwrap();
+                        std::fs::remove_file(&path)?;
                     }
                 }
 
@@ -100,7 +91,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                         "...".bright_black()
                     );
 
-                    jar::install(&server_build, ".", 1).await.unwrap();
+                    jar::install(&server_build, ".", 1).await?;
 
                     println!(
                         "{} {} {} {} {}",
@@ -116,18 +107,14 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                         .unwrap_or(versions.last().unwrap().1)
                         .java
                 } else {
-                    println!(
-                        "{} {} {}",
-                        "server build".red(),
-                        build_id.to_string().cyan(),
-                        "not found!".red()
-                    );
-                    return 1;
+                    return Err(Error::not_found(format!(
+                        "server build `{build_id}` not found"
+                    )));
                 }
             } else {
                 println!("{}", "getting server types...".bright_black());
 
-                let types = api::mcjars::types().await.unwrap();
+                let types = api::mcjars::types().await?;
 
                 println!(
                     "{} {}",
@@ -137,13 +124,10 @@ pub async fn install(matches: &ArgMatches) -> i32 {
 
                 let server_type = if let Some(r#type) = matches.get_one::<String>("type") {
                     if !types.contains_key(&r#type.to_uppercase()) {
-                        println!(
-                            "{} {} {}",
-                            "server type".red(),
-                            r#type.to_string().cyan(),
-                            "not found!".red()
-                        );
-                        return 1;
+                        return Err(Error::not_found(format!(
+                            "server type `{}` not found",
+                            r#type
+                        )));
                     }
 
                     &r#type.to_uppercase()
@@ -153,8 +137,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                         .default(0)
                         .items(&types.values().map(|t| &t.name).collect::<Vec<&String>>())
                         .max_length(10)
-                        .interact()
-                        .unwrap();
+                        .interact()?;
 
                     types.keys().nth(server_type).unwrap()
                 };
@@ -166,7 +149,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                     "...".bright_black()
                 );
 
-                let versions = api::mcjars::versions(server_type).await.unwrap();
+                let versions = api::mcjars::versions(server_type).await?;
 
                 println!(
                     "{} {} {} {}",
@@ -178,13 +161,9 @@ pub async fn install(matches: &ArgMatches) -> i32 {
 
                 let server_version = if let Some(version) = matches.get_one::<String>("version") {
                     if !versions.contains_key(version) {
-                        println!(
-                            "{} {} {}",
-                            "server version".red(),
-                            version.to_string().cyan(),
-                            "not found!".red()
-                        );
-                        return 1;
+                        return Err(Error::not_found(format!(
+                            "server version `{version}` not found"
+                        )));
                     }
 
                     version
@@ -194,8 +173,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                         .default(0)
                         .items(&versions.keys().rev().collect::<Vec<&String>>())
                         .max_length(10)
-                        .interact()
-                        .unwrap();
+                        .interact()?;
 
                     versions.keys().rev().nth(server_version).unwrap()
                 };
@@ -207,9 +185,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                     "...".bright_black()
                 );
 
-                let builds = api::mcjars::builds(server_type, server_version)
-                    .await
-                    .unwrap();
+                let builds = api::mcjars::builds(server_type, server_version).await?;
 
                 println!(
                     "{} {} {} {}",
@@ -225,13 +201,9 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                     } else if let Some(build) = builds.iter().find(|b| &b.name == build) {
                         build
                     } else {
-                        println!(
-                            "{} {} {}",
-                            "server build".red(),
-                            build.to_string().cyan(),
-                            "not found!".red()
-                        );
-                        return 1;
+                        return Err(Error::not_found(format!(
+                            "server build `{build}` not found"
+                        )));
                     }
                 } else {
                     let server_build = FuzzySelect::with_theme(&ColorfulTheme::default())
@@ -239,8 +211,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                         .default(0)
                         .items(&builds.iter().map(|b| &b.name).collect::<Vec<&String>>())
                         .max_length(10)
-                        .interact()
-                        .unwrap();
+                        .interact()?;
 
                     &builds[server_build]
                 };
@@ -253,7 +224,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                     "...".bright_black()
                 );
 
-                jar::install(server_build, ".", 1).await.unwrap();
+                jar::install(server_build, ".", 1).await?;
 
                 println!(
                     "{} {} {} {} {}",
@@ -270,15 +241,14 @@ pub async fn install(matches: &ArgMatches) -> i32 {
             config.modpack_slug = None;
             config.modpack_version = None;
             config.java_version = java;
-            config.save();
+            config.save()?;
         }
         1 => {
             let mut projects = api::modrinth::projects(
                 "",
                 "[[\"project_type:modpack\"],[\"server_side != unsupported\"]]",
             )
-            .await
-            .unwrap();
+            .await?;
             let mut project;
 
             loop {
@@ -303,23 +273,18 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                             .collect::<Vec<String>>(),
                     )
                     .max_length(10)
-                    .interact()
-                    .unwrap();
+                    .interact()?;
 
                 project = modpack;
 
                 if modpack == 0 {
-                    let search = Input::<String>::new()
-                        .with_prompt("Search")
-                        .interact()
-                        .unwrap();
+                    let search = Input::<String>::new().with_prompt("Search").interact()?;
 
                     projects = api::modrinth::projects(
                         &search,
                         "[[\"project_type:modpack\"],[\"server_side != unsupported\"]]",
                     )
-                    .await
-                    .unwrap();
+                    .await?;
                 } else {
                     break;
                 }
@@ -335,9 +300,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                 "...".bright_black()
             );
 
-            let versions = api::modrinth::versions(project.project_id.as_ref().unwrap())
-                .await
-                .unwrap();
+            let versions = api::modrinth::versions(project.project_id.as_ref().unwrap()).await?;
             let versions = versions
                 .iter()
                 .filter(|v| !v.files.is_empty())
@@ -389,8 +352,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                         .collect::<Vec<String>>(),
                 )
                 .max_length(5)
-                .interact()
-                .unwrap();
+                .interact()?;
 
             let modpack_version = &versions[modpack_version];
 
@@ -399,26 +361,20 @@ pub async fn install(matches: &ArgMatches) -> i32 {
             if *wipe {
                 println!("{}", "Wiping server directory...".bright_black());
 
-                let entries = std::fs::read_dir(".").unwrap();
-                for entry in entries {
-                    let entry = entry.unwrap();
-                    let path = entry.path();
+                for entry in std::fs::read_dir(".")? {
+                    let path = entry?.path();
 
                     if path
                         .file_name()
-                        .unwrap()
-                        .to_str()
-                        .unwrap()
-                        .to_string()
-                        .starts_with(".mcvcli")
+                        .is_some_and(|name| name.to_string_lossy().starts_with(".mcvcli"))
                     {
                         continue;
                     }
 
                     if path.is_dir() {
-                        std::fs::remove_dir_all(&path).unwrap();
+                        std::fs::remove_dir_all(&path)?;
                     } else {
-                        std::fs::remove_file(&path).unwrap();
+                        std::fs::remove_file(&path)?;
                     }
                 }
 
@@ -436,7 +392,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                 "...".bright_black()
             );
 
-            modpack::install(".", modpack_version).await;
+            modpack::install(".", modpack_version).await?;
 
             config.jar_file = "server.jar".to_string();
             config.modpack_slug = Some(project.project_id.clone().unwrap());
@@ -454,7 +410,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
                     .java;
             }
 
-            config.save();
+            config.save()?;
 
             println!(
                 "{} {} {} {}",
@@ -467,5 +423,5 @@ pub async fn install(matches: &ArgMatches) -> i32 {
         _ => unreachable!(),
     }
 
-    0
+    Ok(())
 }
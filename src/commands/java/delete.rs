@@ -1,10 +1,11 @@
+use crate::error::{Error, Result};
 use crate::java;
 
 use clap::ArgMatches;
 use colored::Colorize;
 use dialoguer::{FuzzySelect, theme::ColorfulTheme};
 
-pub async fn delete(matches: &ArgMatches) -> i32 {
+pub async fn delete(matches: &ArgMatches) -> Result {
     let version = matches.get_one::<u8>("version");
 
     println!("{}", "listing java versions...".bright_black());
@@ -20,20 +21,15 @@ pub async fn delete(matches: &ArgMatches) -> i32 {
 
     let version = if let Some(version) = version {
         if !list.iter().any(|(v, _)| v == version) {
-            println!(
-                "{} {} {}",
-                "java".bright_black(),
-                version.to_string().cyan(),
-                "not installed".red()
-            );
-            return 1;
+            return Err(Error::not_found(format!(
+                "java `{version}` is not installed"
+            )));
         }
 
         *version
     } else {
         if list.is_empty() {
-            println!("{}", "no java versions to delete".red());
-            return 1;
+            return Err(Error::not_found("no java versions to delete"));
         }
 
         let version = FuzzySelect::with_theme(&ColorfulTheme::default())
@@ -46,8 +42,7 @@ pub async fn delete(matches: &ArgMatches) -> i32 {
             )
             .default(0)
             .max_length(5)
-            .interact()
-            .unwrap();
+            .interact()?;
         println!();
 
         list[version].0
@@ -70,5 +65,5 @@ pub async fn delete(matches: &ArgMatches) -> i32 {
         "DONE".green().bold()
     );
 
-    0
+    Ok(())
 }
@@ -1,15 +1,16 @@
+use crate::error::Result;
 use crate::java;
 
 use clap::ArgMatches;
 use colored::Colorize;
 use dialoguer::{FuzzySelect, theme::ColorfulTheme};
 
-pub async fn install(matches: &ArgMatches) -> i32 {
+pub async fn install(matches: &ArgMatches) -> Result {
     let version = matches.get_one::<u8>("version");
 
     println!("{}", "listing java versions...".bright_black());
 
-    let list: Vec<u8> = java::versions().await.into_iter().rev().collect();
+    let list: Vec<u8> = java::versions().await?.into_iter().rev().collect();
     let installed = java::installed();
 
     println!(
@@ -42,8 +43,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
             )
             .default(0)
             .max_length(5)
-            .interact()
-            .unwrap();
+            .interact()?;
         println!();
 
         list[version]
@@ -75,7 +75,7 @@ pub async fn install(matches: &ArgMatches) -> i32 {
         "...".bright_black()
     );
 
-    java::install(version).await.unwrap();
+    java::install(version).await?;
 
     println!(
         "{} {} {} {}",
@@ -85,5 +85,5 @@ pub async fn install(matches: &ArgMatches) -> i32 {
         "DONE".green().bold()
     );
 
-    0
+    Ok(())
 }
@@ -1,26 +1,43 @@
+use crate::error::Result;
 use crate::{config, java};
 
 use clap::ArgMatches;
 use colored::Colorize;
 use human_bytes::human_bytes;
+use std::path::Path;
 
-fn recursive_size(path: &str) -> u64 {
+fn recursive_size(path: &Path) -> std::io::Result<u64> {
     let mut size = 0;
 
-    for file in std::fs::read_dir(path).unwrap().flatten() {
-        let metadata = file.metadata().unwrap();
+    for file in std::fs::read_dir(path)?.flatten() {
+        let metadata = file.metadata()?;
 
         if metadata.is_dir() {
-            size += recursive_size(file.path().to_str().unwrap());
+            size += recursive_size(&file.path())?;
         } else {
             size += metadata.len();
         }
     }
 
-    size
+    Ok(size)
 }
 
-pub async fn list(_matches: &ArgMatches) -> i32 {
+/// The first line of `java -version`.
+fn version(binary: &str) -> String {
+    std::process::Command::new(binary)
+        .arg("-version")
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .next()
+                .map(str::to_string)
+        })
+        .unwrap_or("unknown".to_string())
+}
+
+pub async fn list(_matches: &ArgMatches) -> Result {
     let config = config::Config::new_optional(".mcvcli.json");
 
     println!("{}", "listing java versions...".bright_black());
@@ -31,20 +48,10 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
 
     let mut versions: Vec<(String, u64)> = Vec::with_capacity(list.len());
     for (_, path) in list.iter() {
-        let version = std::process::Command::new(format!("{path}/bin/java"))
-            .arg("-version")
-            .output()
-            .map(|output| {
-                String::from_utf8(output.stderr)
-                    .unwrap()
-                    .lines()
-                    .next()
-                    .unwrap()
-                    .to_string()
-            })
-            .unwrap_or("unknown".to_string());
-
-        versions.push((version, recursive_size(path)));
+        versions.push((
+            version(&format!("{path}/bin/java")),
+            recursive_size(Path::new(path))?,
+        ));
     }
 
     println!(
@@ -53,7 +60,7 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
         "DONE".green().bold()
     );
 
-    for (i, (version, path)) in list.into_iter().enumerate() {
+    for ((version, path), (java_version, size)) in list.into_iter().zip(versions.iter()) {
         println!();
 
         println!(
@@ -71,10 +78,8 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
             }
         );
 
-        let (version, size) = versions.get(i).unwrap();
-
         println!("  {} {}", "path:   ".bright_black(), path.cyan());
-        println!("  {} {}", "version:".bright_black(), version.cyan());
+        println!("  {} {}", "version:".bright_black(), java_version.cyan());
         println!(
             "  {} {}",
             "size:   ".bright_black(),
@@ -91,18 +96,7 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
             "(local)".green()
         );
 
-        let version = std::process::Command::new(&path)
-            .arg("-version")
-            .output()
-            .map(|output| {
-                String::from_utf8(output.stderr)
-                    .unwrap()
-                    .lines()
-                    .next()
-                    .unwrap()
-                    .to_string()
-            })
-            .unwrap_or("unknown".to_string());
+        let version = self::version(&path);
 
         println!("  {} {}", "path:   ".bright_black(), path.cyan());
         println!("  {} {}", "version:".bright_black(), version.cyan());
@@ -110,12 +104,12 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
             "  {} {}",
             "size:   ".bright_black(),
             if !root.is_empty() {
-                human_bytes(recursive_size(&root) as f64).cyan()
+                human_bytes(recursive_size(Path::new(&root))? as f64).cyan()
             } else {
                 "unknown".cyan()
             }
         );
     }
 
-    0
+    Ok(())
 }
@@ -1,16 +1,17 @@
+use crate::error::Result;
 use crate::{config, java};
 
 use clap::ArgMatches;
 use colored::Colorize;
 use dialoguer::{FuzzySelect, theme::ColorfulTheme};
 
-pub async fn r#use(matches: &ArgMatches) -> i32 {
+pub async fn r#use(matches: &ArgMatches) -> Result {
     let version = matches.get_one::<u8>("version");
-    let mut config = config::Config::new(".mcvcli.json", false);
+    let mut config = config::Config::new(".mcvcli.json", false)?;
 
     println!("{}", "listing java versions...".bright_black());
 
-    let list: Vec<u8> = java::versions().await.into_iter().rev().collect();
+    let list: Vec<u8> = java::versions().await?.into_iter().rev().collect();
 
     println!(
         "{} {}",
@@ -42,8 +43,7 @@ pub async fn r#use(matches: &ArgMatches) -> i32 {
             )
             .default(0)
             .max_length(5)
-            .interact()
-            .unwrap();
+            .interact()?;
         println!();
 
         list[version]
@@ -64,7 +64,7 @@ pub async fn r#use(matches: &ArgMatches) -> i32 {
             "...".bright_black()
         );
 
-        java::install(version).await.unwrap();
+        java::install(version).await?;
 
         println!(
             "{} {} {} {}",
@@ -76,7 +76,7 @@ pub async fn r#use(matches: &ArgMatches) -> i32 {
     }
 
     config.java_version = version;
-    config.save();
+    config.save()?;
 
     println!(
         "{} {} {} {}",
@@ -86,5 +86,5 @@ pub async fn r#use(matches: &ArgMatches) -> i32 {
         "DONE".green().bold()
     );
 
-    0
+    Ok(())
 }
@@ -0,0 +1,205 @@
+use crate::error::{Error, Result};
+use crate::logs::{self, Entry, Level};
+
+use chrono::{Local, NaiveDateTime};
+use clap::ArgMatches;
+use colored::Colorize;
+use regex::Regex;
+use std::{
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+struct Filter {
+    since: Option<NaiveDateTime>,
+    until: Option<NaiveDateTime>,
+    level: Option<Level>,
+    thread: Option<String>,
+    grep: Option<Regex>,
+}
+
+impl Filter {
+    fn matches(&self, entry: &Entry) -> bool {
+        if let Some(level) = self.level
+            && entry.level.is_none_or(|entry_level| entry_level < level)
+        {
+            return false;
+        }
+
+        if let Some(since) = self.since
+            && entry.time.is_none_or(|time| time < since)
+        {
+            return false;
+        }
+
+        if let Some(until) = self.until
+            && entry.time.is_none_or(|time| time > until)
+        {
+            return false;
+        }
+
+        if let Some(thread) = &self.thread
+            && entry
+                .thread
+                .as_ref()
+                .is_none_or(|entry_thread| !entry_thread.contains(thread.as_str()))
+        {
+            return false;
+        }
+
+        if let Some(grep) = &self.grep
+            && !grep.is_match(&entry.message)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+fn print(entry: &Entry) {
+    for line in entry.lines.iter() {
+        match entry.level {
+            Some(Level::Warn) => println!("{}", line.yellow()),
+            Some(Level::Error | Level::Fatal) => println!("{}", line.red()),
+            _ => println!("{line}"),
+        }
+    }
+}
+
+pub async fn logs(matches: &ArgMatches) -> Result {
+    let follow = *matches.get_one::<bool>("follow").expect("required");
+    let all = *matches.get_one::<bool>("all").expect("required");
+    let lines = matches.get_one::<usize>("lines").copied();
+
+    let grep = match matches
+        .get_one::<String>("grep")
+        .map(|grep| Regex::new(grep))
+    {
+        Some(Ok(grep)) => Some(grep),
+        Some(Err(err)) => return Err(Error::other(format!("invalid regex: {err}"))),
+        None => None,
+    };
+
+    let filter = Filter {
+        since: matches.get_one::<NaiveDateTime>("since").copied(),
+        until: matches.get_one::<NaiveDateTime>("until").copied(),
+        level: matches.get_one::<Level>("level").copied(),
+        thread: matches.get_one::<String>("thread").cloned(),
+        grep,
+    };
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    if all || filter.since.is_some() || filter.until.is_some() {
+        for (date, path) in logs::archives() {
+            if filter.since.is_none_or(|since| date >= since.date()) {
+                files.push(path);
+            }
+        }
+    }
+
+    let latest = Path::new(logs::LATEST);
+    if latest.exists() {
+        files.push(latest.to_path_buf());
+    }
+
+    if files.is_empty() {
+        return Err(Error::not_found(format!(
+            "no logs found in `{}` directory",
+            logs::DIRECTORY
+        )));
+    }
+
+    let mut entries = Vec::new();
+    let mut offset = 0;
+
+    for path in files.iter() {
+        let content = match logs::read(path) {
+            Ok(content) => content,
+            Err(err) => {
+                println!(
+                    "{} {}{} {}",
+                    "unable to read".red(),
+                    path.to_string_lossy().cyan(),
+                    ":".red(),
+                    err.to_string().red()
+                );
+                continue;
+            }
+        };
+
+        if path == latest {
+            offset = content.len() as u64;
+        }
+
+        entries.extend(
+            logs::parse(&content, logs::date(path))
+                .into_iter()
+                .filter(|entry| filter.matches(entry)),
+        );
+    }
+
+    if let Some(lines) = lines {
+        entries.drain(..entries.len().saturating_sub(lines));
+    }
+
+    for entry in entries.iter() {
+        print(entry);
+    }
+
+    if !follow {
+        return Ok(());
+    }
+
+    let mut buffer = String::new();
+    let mut printed = false;
+
+    loop {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let Ok(mut file) = std::fs::File::open(latest) else {
+            continue;
+        };
+        let length = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+
+        // the server started a new latest.log
+        if length < offset {
+            offset = 0;
+            buffer.clear();
+        }
+
+        if length == offset {
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        if file.seek(SeekFrom::Start(offset)).is_err() || file.read_to_end(&mut bytes).is_err() {
+            continue;
+        }
+
+        offset += bytes.len() as u64;
+        buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+        // only parse complete lines, the rest is kept for the next read
+        let Some(end) = buffer.rfind('\n') else {
+            continue;
+        };
+        let chunk = buffer.drain(..=end).collect::<String>();
+
+        for entry in logs::parse(&chunk, Local::now().date_naive()) {
+            // lines continuing an entry of the previous chunk
+            if entry.time.is_none() && entry.level.is_none() {
+                if printed {
+                    print(&entry);
+                }
+                continue;
+            }
+
+            printed = filter.matches(&entry);
+            if printed {
+                print(&entry);
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::{Error, Result};
 use crate::{api, config};
 
 use clap::ArgMatches;
@@ -58,9 +59,9 @@ struct Custom {
     play_time: Option<u32>,
 }
 
-pub async fn lookup(matches: &ArgMatches) -> i32 {
+pub async fn lookup(matches: &ArgMatches) -> Result {
     let player = matches.get_one::<String>("player").expect("required");
-    let _config = config::Config::new(".mcvcli.json", false);
+    let _config = config::Config::new(".mcvcli.json", false)?;
 
     println!("{}", "looking up player...".bright_black());
 
@@ -69,14 +70,24 @@ pub async fn lookup(matches: &ArgMatches) -> i32 {
         None => api::mojang::get_profile_name(player).await,
     };
 
-    if player.is_err() {
-        println!(
-            "{} {}",
-            "looking up player...".bright_black(),
-            "FAILED".red().bold()
-        );
-        return 1;
-    }
+    let player = match player {
+        Ok(player) => player,
+        Err(err) => {
+            println!(
+                "{} {}",
+                "looking up player...".bright_black(),
+                "FAILED".red().bold()
+            );
+
+            return Err(
+                if err.status() == Some(reqwest::StatusCode::NOT_FOUND) || err.is_decode() {
+                    Error::not_found("player not found")
+                } else {
+                    err.into()
+                },
+            );
+        }
+    };
 
     println!(
         "{} {}",
@@ -85,8 +96,6 @@ pub async fn lookup(matches: &ArgMatches) -> i32 {
     );
     println!();
 
-    let player = player.unwrap();
-
     println!("{}", player.name.cyan().bold());
     println!(
         "  {} {}",
@@ -105,7 +114,7 @@ pub async fn lookup(matches: &ArgMatches) -> i32 {
             "player data:".bright_black(),
             "not found".red().bold()
         );
-        return 0;
+        return Ok(());
     }
 
     println!("  {}", "player data:".bright_black());
@@ -113,10 +122,9 @@ pub async fn lookup(matches: &ArgMatches) -> i32 {
     let player_data = File::open(format!(
         "world/playerdata/{}.dat",
         api::mojang::format_uuid(&player.id).unwrap()
-    ))
-    .unwrap();
+    ))?;
     let player_data = GzDecoder::new(BufReader::new(player_data));
-    let player_data: Result<PlayerData, _> = fastnbt::from_reader(player_data);
+    let player_data: std::result::Result<PlayerData, _> = fastnbt::from_reader(player_data);
 
     let player_stats: Option<PlayerStats> = match File::open(format!(
         "world/stats/{}.json",
@@ -132,7 +140,10 @@ pub async fn lookup(matches: &ArgMatches) -> i32 {
             "player data:".bright_black(),
             "unable to read".red().bold()
         );
-        return 1;
+        return Err(Error::io(format!(
+            "unable to read `world/playerdata/{}.dat`",
+            api::mojang::format_uuid(&player.id).unwrap()
+        )));
     }
 
     let player_data = player_data.unwrap();
@@ -233,5 +244,5 @@ pub async fn lookup(matches: &ArgMatches) -> i32 {
         }
     }
 
-    0
+    Ok(())
 }
@@ -0,0 +1,154 @@
+use crate::error::Result;
+use crate::properties::{self, Properties};
+use crate::registry::Registry;
+use crate::{config, detached, jar};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use serde::Serialize;
+use std::{path::Path, time::Duration};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Server {
+    name: String,
+    path: String,
+    exists: bool,
+    profile: Option<String>,
+    r#type: Option<String>,
+    version: Option<String>,
+    running: bool,
+    pid: Option<usize>,
+    port: Option<u16>,
+    #[serde(rename = "ramMB")]
+    ram_mb: Option<u32>,
+}
+
+pub async fn ls(matches: &ArgMatches) -> Result {
+    let json = *matches.get_one::<bool>("json").expect("required");
+    let registry = Registry::load();
+
+    if !json {
+        println!("{}", "listing servers...".bright_black());
+    }
+
+    let servers = futures::future::join_all(registry.servers.iter().map(|server| async move {
+        let config = config::Config::new_optional(&format!("{}/.mcvcli.json", server.path));
+
+        let detected = match &config {
+            Some(config) => {
+                tokio::time::timeout(Duration::from_secs(10), jar::detect(&server.path, config))
+                    .await
+                    .ok()
+                    .flatten()
+            }
+            None => None,
+        };
+        let running = config.as_ref().is_some_and(detached::running);
+
+        Server {
+            name: server.name.clone(),
+            path: server.path.clone(),
+            exists: config.is_some(),
+            profile: config.as_ref().map(|config| config.profile_name.clone()),
+            r#type: detected
+                .as_ref()
+                .map(|([build, _], _, _)| build.r#type.clone()),
+            version: detected
+                .and_then(|([build, _], _, _)| build.version_id.or(build.project_version_id)),
+            running,
+            pid: config
+                .as_ref()
+                .and_then(|config| config.pid)
+                .filter(|_| running),
+            port: config.as_ref().map(|_| {
+                Properties::new(
+                    Path::new(&server.path)
+                        .join(properties::FILE)
+                        .to_str()
+                        .unwrap(),
+                )
+                .unwrap_or_default()
+                .server_address()
+                .1
+            }),
+            ram_mb: config.as_ref().map(|config| config.ram_mb),
+        }
+    }))
+    .await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&servers).unwrap());
+        return Ok(());
+    }
+
+    println!(
+        "{} {}",
+        "listing servers...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    if servers.is_empty() {
+        println!();
+        println!(
+            "{} {}",
+            "no servers registered, use".bright_black(),
+            "mcvcli register".cyan()
+        );
+    }
+
+    for server in servers {
+        println!();
+        println!(
+            "{} {}",
+            server.name.cyan().bold().underline(),
+            if !server.exists {
+                "(missing)".red()
+            } else if server.running {
+                "(running)".green()
+            } else {
+                "(stopped)".bright_black()
+            }
+        );
+        println!("  {} {}", "path:    ".bright_black(), server.path.cyan());
+
+        if !server.exists {
+            continue;
+        }
+
+        println!(
+            "  {} {}",
+            "profile: ".bright_black(),
+            server.profile.unwrap_or_default().cyan()
+        );
+        println!(
+            "  {} {}",
+            "version: ".bright_black(),
+            match (server.r#type, server.version) {
+                (Some(r#type), Some(version)) => format!("{type} {version}"),
+                (Some(r#type), None) => r#type,
+                _ => "unknown".to_string(),
+            }
+            .cyan()
+        );
+        if let Some(pid) = server.pid {
+            println!(
+                "  {} {}",
+                "pid:     ".bright_black(),
+                pid.to_string().cyan()
+            );
+        }
+        println!(
+            "  {} {}",
+            "port:    ".bright_black(),
+            server.port.unwrap_or_default().to_string().cyan()
+        );
+        println!(
+            "  {} {}",
+            "ram (mb):".bright_black(),
+            server.ram_mb.unwrap_or_default().to_string().cyan()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::config;
+use crate::error::{Error, Result};
+use crate::metrics::Exporter;
+
+use axum::{Router, extract::State, http::header::CONTENT_TYPE, routing::get};
+use clap::ArgMatches;
+use colored::Colorize;
+use std::{net::SocketAddr, sync::Arc};
+
+async fn scrape(
+    State(exporter): State<Arc<Exporter>>,
+) -> ([(&'static str, &'static str); 1], String) {
+    (
+        [(CONTENT_TYPE.as_str(), "text/plain; version=0.0.4")],
+        exporter.render().await,
+    )
+}
+
+pub async fn metrics(matches: &ArgMatches) -> Result {
+    let listen = *matches.get_one::<SocketAddr>("listen").expect("required");
+    let all = *matches.get_one::<bool>("all").expect("required");
+
+    if !all {
+        config::Config::new(".mcvcli.json", false)?;
+    }
+
+    println!(
+        "{} {} {}",
+        "listening on".bright_black(),
+        listen.to_string().cyan(),
+        "...".bright_black()
+    );
+
+    let listener = match tokio::net::TcpListener::bind(listen).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            println!(
+                "{} {} {} {}",
+                "listening on".bright_black(),
+                listen.to_string().cyan(),
+                "...".bright_black(),
+                "FAILED".red().bold()
+            );
+            return Err(Error::io(err));
+        }
+    };
+
+    println!(
+        "{} {} {} {}",
+        "listening on".bright_black(),
+        listen.to_string().cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+    println!(
+        "{} {}",
+        "metrics are served on".bright_black(),
+        format!("http://{listen}/metrics").cyan()
+    );
+
+    let router = Router::new()
+        .route("/metrics", get(scrape))
+        .with_state(Arc::new(Exporter::new(all)));
+
+    tokio::select! {
+        result = axum::serve(listener, router) => {
+            if let Err(err) = result {
+                return Err(Error::io(err));
+            }
+        }
+        _ = tokio::signal::ctrl_c() => {}
+    }
+
+    Ok(())
+}
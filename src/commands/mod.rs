@@ -1,14 +1,30 @@
 pub mod attach;
 pub mod config;
+pub mod crash;
+pub mod dashboard;
+pub mod exec;
 pub mod init;
 pub mod install;
 pub mod java;
+pub mod logs;
 pub mod lookup;
+pub mod ls;
+pub mod metrics;
 pub mod mods;
+pub mod players;
 pub mod profile;
 pub mod query;
+pub mod rcon;
+pub mod register;
+pub mod restart;
+pub mod schedule;
+pub mod serve;
+pub mod service;
 pub mod start;
 pub mod status;
 pub mod stop;
+pub mod supervise;
+pub mod token;
+pub mod unregister;
 pub mod update;
 pub mod version;
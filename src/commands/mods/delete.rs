@@ -1,3 +1,4 @@
+use crate::error::{Error, Result};
 use crate::{api, config};
 
 use clap::ArgMatches;
@@ -5,17 +6,16 @@ use colored::Colorize;
 use dialoguer::{MultiSelect, theme::ColorfulTheme};
 use std::path::Path;
 
-pub async fn delete(_matches: &ArgMatches) -> i32 {
-    let _config = config::Config::new(".mcvcli.json", false);
+pub async fn delete(_matches: &ArgMatches) -> Result {
+    let _config = config::Config::new(".mcvcli.json", false)?;
 
     if !Path::new("mods").exists() {
-        println!("{}", "no mods folder found.".red());
-        return 1;
+        return Err(Error::not_found("no mods folder found"));
     }
 
     println!("{}", "listing mods...".bright_black());
 
-    let list = api::modrinth::lookup("mods", None, None).await.unwrap();
+    let list = api::modrinth::lookup("mods", None, None).await?;
 
     println!(
         "{} {}",
@@ -25,8 +25,7 @@ pub async fn delete(_matches: &ArgMatches) -> i32 {
     println!();
 
     if list.is_empty() {
-        println!("{}", "no mods to delete".red());
-        return 1;
+        return Err(Error::not_found("no mods to delete"));
     }
 
     let mods = MultiSelect::with_theme(&ColorfulTheme::default())
@@ -38,8 +37,7 @@ pub async fn delete(_matches: &ArgMatches) -> i32 {
                 .collect::<Vec<String>>(),
         )
         .max_length(10)
-        .interact()
-        .unwrap();
+        .interact()?;
     println!();
 
     for i in mods {
@@ -51,7 +49,7 @@ pub async fn delete(_matches: &ArgMatches) -> i32 {
             "...".bright_black(),
         );
 
-        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(path)?;
 
         println!(
             "{} {} {} {}",
@@ -62,5 +60,5 @@ pub async fn delete(_matches: &ArgMatches) -> i32 {
         );
     }
 
-    0
+    Ok(())
 }
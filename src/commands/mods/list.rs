@@ -1,15 +1,15 @@
+use crate::error::{Error, Result};
 use crate::{api, config, jar};
 
 use clap::ArgMatches;
 use colored::Colorize;
 use std::path::Path;
 
-pub async fn list(_matches: &ArgMatches) -> i32 {
-    let config = config::Config::new(".mcvcli.json", false);
+pub async fn list(_matches: &ArgMatches) -> Result {
+    let config = config::Config::new(".mcvcli.json", false)?;
 
     if !Path::new("mods").exists() {
-        println!("{}", "no mods folder found.".red());
-        return 1;
+        return Err(Error::not_found("no mods folder found"));
     }
 
     println!("{}", "checking installed version ...".bright_black());
@@ -23,8 +23,7 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
     );
 
     if detected.is_none() {
-        println!("{}", "installed version could not be detected.".red());
-        return 1;
+        return Err(Error::other("installed version could not be detected"));
     }
 
     println!("{}", "listing mods...".bright_black());
@@ -42,8 +41,7 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
             ),
         ),
     )
-    .await
-    .unwrap();
+    .await?;
 
     println!(
         "{} {}",
@@ -89,14 +87,13 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
                         .unwrap()
                 )
                 .cyan(),
-            if project.installed_latest_version.is_none() || project.installed_version.is_none() {
-                "(unknown)".yellow()
-            } else if project.installed_latest_version.as_ref().unwrap().id
-                == project.installed_version.as_ref().unwrap().id
-            {
-                "(latest)".green()
-            } else {
-                "(outdated)".red()
+            match (
+                &project.installed_latest_version,
+                &project.installed_version
+            ) {
+                (Some(latest), Some(installed)) if latest.id == installed.id => "(latest)".green(),
+                (Some(_), Some(_)) => "(outdated)".red(),
+                _ => "(unknown)".yellow(),
             }
         );
     }
@@ -123,5 +120,5 @@ pub async fn list(_matches: &ArgMatches) -> i32 {
             .cyan()
     );
 
-    0
+    Ok(())
 }
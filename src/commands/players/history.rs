@@ -0,0 +1,128 @@
+use crate::error::{Error, Result};
+use crate::players::{self, Death, Session};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct History {
+    name: String,
+    first_seen: i64,
+    last_seen: i64,
+    online: bool,
+    playtime: i64,
+    chats: u32,
+    deaths: Vec<Death>,
+    sessions: Vec<Session>,
+}
+
+pub async fn history(matches: &ArgMatches) -> Result {
+    let name = matches.get_one::<String>("name").expect("required");
+    let limit = *matches.get_one::<usize>("limit").expect("required");
+    let json = *matches.get_one::<bool>("json").expect("required");
+
+    let store = players::load();
+    let now = chrono::Utc::now().timestamp();
+
+    let sessions = store
+        .sessions
+        .into_iter()
+        .filter(|session| session.name.eq_ignore_ascii_case(name))
+        .collect::<Vec<_>>();
+
+    let (Some(first), Some(last)) = (sessions.first(), sessions.last()) else {
+        return Err(Error::not_found(format!(
+            "player `{name}` has no recorded sessions"
+        )));
+    };
+
+    let history = History {
+        name: last.name.clone(),
+        first_seen: first.joined,
+        last_seen: last.left.unwrap_or(now),
+        online: last.left.is_none(),
+        playtime: sessions.iter().map(|session| session.duration(now)).sum(),
+        chats: sessions.iter().map(|session| session.chats).sum(),
+        deaths: sessions
+            .iter()
+            .flat_map(|session| session.deaths.iter().cloned())
+            .collect(),
+        sessions: sessions.iter().rev().take(limit).cloned().collect(),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&history).unwrap());
+        return Ok(());
+    }
+
+    println!(
+        "{} {}",
+        history.name.cyan().bold().underline(),
+        if history.online {
+            "(online)".green()
+        } else {
+            "(offline)".bright_black()
+        }
+    );
+    println!(
+        "  {} {}",
+        "first seen:".bright_black(),
+        players::format_time(history.first_seen).cyan()
+    );
+    println!(
+        "  {} {}",
+        "last seen: ".bright_black(),
+        players::format_time(history.last_seen).cyan()
+    );
+    println!(
+        "  {} {}",
+        "playtime:  ".bright_black(),
+        players::format_duration(history.playtime).cyan()
+    );
+    println!(
+        "  {} {}",
+        "sessions:  ".bright_black(),
+        sessions.len().to_string().cyan()
+    );
+    println!(
+        "  {} {}",
+        "chats:     ".bright_black(),
+        history.chats.to_string().cyan()
+    );
+    println!(
+        "  {} {}",
+        "deaths:    ".bright_black(),
+        history.deaths.len().to_string().cyan()
+    );
+
+    println!();
+    println!("{}", "recent sessions:".bright_black());
+    for session in history.sessions.iter() {
+        println!(
+            "  {} {} {}",
+            players::format_time(session.joined).cyan(),
+            players::format_duration(session.duration(now)),
+            if session.left.is_none() {
+                "(online)".green()
+            } else {
+                String::new().green()
+            }
+        );
+    }
+
+    if !history.deaths.is_empty() {
+        println!();
+        println!("{}", "recent deaths:".bright_black());
+        for death in history.deaths.iter().rev().take(limit) {
+            println!(
+                "  {} {}",
+                players::format_time(death.time).cyan(),
+                death.message
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod history;
+pub mod online;
+pub mod sessions;
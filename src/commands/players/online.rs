@@ -0,0 +1,77 @@
+use crate::error::Result;
+use crate::{config, detached, players};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Player {
+    name: String,
+    joined: i64,
+    duration: i64,
+}
+
+pub async fn online(matches: &ArgMatches) -> Result {
+    let json = *matches.get_one::<bool>("json").expect("required");
+    let config = config::Config::new(".mcvcli.json", false)?;
+
+    let mut store = players::load();
+    let running = detached::running(&config);
+
+    // sessions of a server that is not running anymore, e.g. from an imported latest.log
+    if !running
+        && store.close(None)
+        && let Some(_lock) = players::lock(false)
+    {
+        store.save();
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let online = store
+        .online()
+        .map(|session| Player {
+            name: session.name.clone(),
+            joined: session.joined,
+            duration: session.duration(now),
+        })
+        .collect::<Vec<_>>();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&online).unwrap());
+        return Ok(());
+    }
+
+    if !running {
+        println!(
+            "{} {}",
+            "server is not running, use".red(),
+            "mcvcli players sessions".cyan()
+        );
+        return Ok(());
+    }
+
+    if online.is_empty() {
+        println!("{}", "no players online".bright_black());
+        return Ok(());
+    }
+
+    println!(
+        "{} {}",
+        online.len().to_string().cyan(),
+        "players online".bright_black()
+    );
+
+    for player in online {
+        println!(
+            "  {} {} {} {}",
+            player.name.cyan().bold(),
+            "online for".bright_black(),
+            players::format_duration(player.duration).cyan(),
+            format!("(since {})", players::format_time(player.joined)).bright_black()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::error::Result;
+use crate::players;
+
+use clap::ArgMatches;
+use colored::Colorize;
+use std::collections::HashSet;
+
+pub async fn sessions(matches: &ArgMatches) -> Result {
+    let since = players::timestamp(
+        *matches
+            .get_one::<chrono::NaiveDateTime>("since")
+            .expect("required"),
+    );
+    let json = *matches.get_one::<bool>("json").expect("required");
+
+    let store = players::load();
+    let now = chrono::Utc::now().timestamp();
+
+    let sessions = store
+        .sessions
+        .into_iter()
+        .rev()
+        .filter(|session| session.left.unwrap_or(now) >= since)
+        .collect::<Vec<_>>();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&sessions).unwrap());
+        return Ok(());
+    }
+
+    if sessions.is_empty() {
+        println!("{}", "no sessions in this time range".bright_black());
+        return Ok(());
+    }
+
+    for session in sessions.iter() {
+        println!(
+            "{} {} {} {}",
+            players::format_time(session.joined).bright_black(),
+            format!("{:<16}", session.name).cyan(),
+            players::format_duration(session.duration(now)),
+            if session.left.is_none() {
+                "(online)".green()
+            } else {
+                String::new().green()
+            }
+        );
+    }
+
+    println!();
+    println!("{}", "summary:".bright_black());
+    println!(
+        "  {} {}",
+        "sessions:".bright_black(),
+        sessions.len().to_string().cyan()
+    );
+    println!(
+        "  {} {}",
+        "players: ".bright_black(),
+        sessions
+            .iter()
+            .map(|session| session.name.as_str())
+            .collect::<HashSet<_>>()
+            .len()
+            .to_string()
+            .cyan()
+    );
+    println!(
+        "  {} {}",
+        "playtime:".bright_black(),
+        players::format_duration(sessions.iter().map(|session| session.duration(now)).sum()).cyan()
+    );
+
+    Ok(())
+}
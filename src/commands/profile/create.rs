@@ -1,30 +1,21 @@
-use crate::{commands, config, profiles};
+use crate::error::{Error, Result};
+use crate::{commands, config, profiles, registry};
 
 use clap::ArgMatches;
 use colored::Colorize;
 
-pub async fn create(matches: &ArgMatches) -> i32 {
+pub async fn create(matches: &ArgMatches) -> Result {
     let name = matches.get_one::<String>("name").unwrap();
-    let config = config::Config::new(".mcvcli.json", false);
+    let config = config::Config::new(".mcvcli.json", false)?;
 
     if profiles::list().contains(name) {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            name.cyan(),
-            "already exists!".red()
-        );
-        return 1;
+        return Err(Error::other(format!("profile `{name}` already exists")));
     }
 
     if config.profile_name == *name {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            name.cyan(),
-            "is currently in use!".red()
-        );
-        return 1;
+        return Err(Error::other(format!(
+            "profile `{name}` is currently in use"
+        )));
     }
 
     println!(
@@ -35,7 +26,7 @@ pub async fn create(matches: &ArgMatches) -> i32 {
     );
 
     let directory = format!(".mcvcli.profiles/{name}");
-    commands::init::init(matches, Some(&directory), Some(name)).await;
+    commands::init::init(matches, Some(&directory), Some(name)).await?;
 
     println!(
         "{} {} {} {}",
@@ -45,5 +36,9 @@ pub async fn create(matches: &ArgMatches) -> i32 {
         "DONE".green().bold()
     );
 
-    0
+    if let Some(name) = registry::register(&std::env::current_dir().unwrap()) {
+        println!("{} {}", "registered server as".bright_black(), name.cyan());
+    }
+
+    Ok(())
 }
@@ -1,12 +1,13 @@
+use crate::error::{Error, Result};
 use crate::{config, profiles};
 
 use clap::ArgMatches;
 use colored::Colorize;
 use dialoguer::{Confirm, FuzzySelect, theme::ColorfulTheme};
 
-pub async fn delete(matches: &ArgMatches) -> i32 {
+pub async fn delete(matches: &ArgMatches) -> Result {
     let name = matches.get_one::<String>("name");
-    let config = config::Config::new(".mcvcli.json", false);
+    let config = config::Config::new(".mcvcli.json", false)?;
 
     let list = profiles::list();
 
@@ -14,8 +15,7 @@ pub async fn delete(matches: &ArgMatches) -> i32 {
         name
     } else {
         if list.is_empty() {
-            println!("{}", "no profiles to delete".red());
-            return 1;
+            return Err(Error::not_found("no profiles to delete"));
         }
 
         let name = FuzzySelect::with_theme(&ColorfulTheme::default())
@@ -23,40 +23,28 @@ pub async fn delete(matches: &ArgMatches) -> i32 {
             .items(&list)
             .default(0)
             .max_length(5)
-            .interact()
-            .unwrap();
+            .interact()?;
 
         &list[name]
     };
 
     if config.profile_name == *name {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            name.cyan(),
-            "is currently in use!".red()
-        );
-        return 1;
+        return Err(Error::other(format!(
+            "profile `{name}` is currently in use"
+        )));
     }
 
     if !list.contains(name) {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            name.cyan(),
-            "does not exist!".red()
-        );
-        return 1;
+        return Err(Error::not_found(format!("profile `{name}` does not exist")));
     }
 
     let confirm = Confirm::with_theme(&ColorfulTheme::default())
         .with_prompt("Are you sure you want to delete this profile?")
         .default(false)
-        .interact()
-        .unwrap();
+        .interact()?;
 
     if !confirm {
-        return 1;
+        return Err(Error::Aborted);
     }
 
     println!(
@@ -67,7 +55,7 @@ pub async fn delete(matches: &ArgMatches) -> i32 {
     );
 
     let directory = format!(".mcvcli.profiles/{name}");
-    std::fs::remove_dir_all(directory).unwrap();
+    std::fs::remove_dir_all(directory)?;
 
     println!(
         "{} {} {} {}",
@@ -77,5 +65,5 @@ pub async fn delete(matches: &ArgMatches) -> i32 {
         "DONE".green().bold()
     );
 
-    0
+    Ok(())
 }
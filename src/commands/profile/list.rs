@@ -1,12 +1,13 @@
+use crate::error::Result;
 use crate::{config, jar, profiles};
 
 use clap::ArgMatches;
 use colored::Colorize;
 use std::path::Path;
 
-pub async fn list(matches: &ArgMatches) -> i32 {
+pub async fn list(matches: &ArgMatches) -> Result {
     let include_version = matches.get_one::<bool>("include_version").unwrap();
-    let config = config::Config::new(".mcvcli.json", false);
+    let config = config::Config::new(".mcvcli.json", false)?;
 
     println!("{}", "listing profiles...".bright_black());
 
@@ -27,7 +28,8 @@ pub async fn list(matches: &ArgMatches) -> i32 {
                 let profile_config = config::Config::new(
                     Path::new(&directory).join(".mcvcli.json").to_str().unwrap(),
                     false,
-                );
+                )
+                .ok()?;
 
                 jar::detect(&directory, &profile_config).await
             });
@@ -54,7 +56,7 @@ pub async fn list(matches: &ArgMatches) -> i32 {
         let profile_config = config::Config::new(
             Path::new(&directory).join(".mcvcli.json").to_str().unwrap(),
             false,
-        );
+        )?;
 
         println!(
             "{} {}",
@@ -160,5 +162,5 @@ pub async fn list(matches: &ArgMatches) -> i32 {
         }
     }
 
-    0
+    Ok(())
 }
@@ -1,3 +1,4 @@
+use crate::error::{Error, Result};
 use crate::{config, detached, profiles};
 
 use clap::ArgMatches;
@@ -5,17 +6,12 @@ use colored::Colorize;
 use dialoguer::{FuzzySelect, theme::ColorfulTheme};
 use std::path::Path;
 
-pub async fn r#use(matches: &ArgMatches) -> i32 {
+pub async fn r#use(matches: &ArgMatches) -> Result {
     let name = matches.get_one::<String>("name");
-    let config = config::Config::new(".mcvcli.json", false);
-
-    if detached::status(config.pid) {
-        println!(
-            "{} {}",
-            "server is currently running, use".red(),
-            "mcvcli stop".cyan()
-        );
-        return 1;
+    let config = config::Config::new(".mcvcli.json", false)?;
+
+    if detached::running(&config) {
+        return Err(Error::running());
     }
 
     let list = profiles::list();
@@ -24,8 +20,7 @@ pub async fn r#use(matches: &ArgMatches) -> i32 {
         name
     } else {
         if list.is_empty() {
-            println!("{}", "no profiles to use".red());
-            return 1;
+            return Err(Error::not_found("no profiles to use"));
         }
 
         let name = FuzzySelect::with_theme(&ColorfulTheme::default())
@@ -33,31 +28,20 @@ pub async fn r#use(matches: &ArgMatches) -> i32 {
             .items(&list)
             .default(0)
             .max_length(5)
-            .interact()
-            .unwrap();
+            .interact()?;
         println!();
 
         &list[name]
     };
 
     if config.profile_name == *name {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            name.cyan(),
-            "is currently in use!".red()
-        );
-        return 1;
+        return Err(Error::other(format!(
+            "profile `{name}` is currently in use"
+        )));
     }
 
     if !list.contains(name) {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            name.cyan(),
-            "does not exist!".red()
-        );
-        return 1;
+        return Err(Error::not_found(format!("profile `{name}` does not exist")));
     }
 
     println!(
@@ -71,35 +55,22 @@ pub async fn r#use(matches: &ArgMatches) -> i32 {
     let old_directory = format!(".mcvcli.profiles/{name}");
 
     if !Path::new(&new_directory).exists() {
-        std::fs::create_dir_all(&new_directory).unwrap();
+        std::fs::create_dir_all(&new_directory)?;
     }
 
-    for entry in std::fs::read_dir(".").unwrap().flatten() {
-        let path = entry.path();
-
-        if path.file_name().unwrap() == ".mcvcli.profiles" {
+    for entry in std::fs::read_dir(".")?.flatten() {
+        if entry.file_name() == ".mcvcli.profiles" {
             continue;
         }
 
         std::fs::rename(
-            &path,
-            format!(
-                "{}/{}",
-                new_directory,
-                path.file_name().unwrap().to_str().unwrap()
-            ),
-        )
-        .unwrap();
+            entry.path(),
+            Path::new(&new_directory).join(entry.file_name()),
+        )?;
     }
 
-    for entry in std::fs::read_dir(&old_directory).unwrap().flatten() {
-        let path = entry.path();
-
-        std::fs::rename(
-            &path,
-            format!("./{}", path.file_name().unwrap().to_str().unwrap()),
-        )
-        .unwrap();
+    for entry in std::fs::read_dir(&old_directory)?.flatten() {
+        std::fs::rename(entry.path(), entry.file_name())?;
     }
 
     println!(
@@ -110,5 +81,5 @@ pub async fn r#use(matches: &ArgMatches) -> i32 {
         "DONE".green().bold()
     );
 
-    0
+    Ok(())
 }
@@ -1,7 +1,9 @@
+use crate::error::{Error, Result};
+
 use clap::ArgMatches;
 use colored::Colorize;
 
-pub async fn query(matches: &ArgMatches) -> i32 {
+pub async fn query(matches: &ArgMatches) -> Result {
     let mut address = matches
         .get_one::<String>("address")
         .expect("required")
@@ -29,10 +31,7 @@ pub async fn query(matches: &ArgMatches) -> i32 {
     if *use_query {
         let status = match server.query_full() {
             Ok(status) => status,
-            Err(e) => {
-                println!("{}: {}", "Error".red(), e);
-                return 1;
-            }
+            Err(err) => return Err(Error::Network(err.to_string())),
         };
 
         println!("{status:?}");
@@ -89,10 +88,7 @@ pub async fn query(matches: &ArgMatches) -> i32 {
                         Err(_) => {
                             let status = match server.get_beta_legacy_server_status() {
                                 Ok(status) => status,
-                                Err(e) => {
-                                    println!("{}: {}", "Error".red(), e);
-                                    return 1;
-                                }
+                                Err(err) => return Err(Error::Network(err.to_string())),
                             };
 
                             println!(
@@ -117,7 +113,7 @@ pub async fn query(matches: &ArgMatches) -> i32 {
                                 status.max_players.to_string().cyan()
                             );
 
-                            return 0;
+                            return Ok(());
                         }
                     },
                 };
@@ -163,5 +159,5 @@ pub async fn query(matches: &ArgMatches) -> i32 {
         };
     }
 
-    0
+    Ok(())
 }
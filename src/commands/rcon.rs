@@ -0,0 +1,135 @@
+use crate::config;
+use crate::error::{Error, Result};
+use crate::rcon::{Address, Rcon};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use std::io::Write;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// Removes the `§` formatting codes minecraft puts into command output.
+fn strip_formatting(output: &str) -> String {
+    let mut result = String::with_capacity(output.len());
+    let mut chars = output.chars();
+
+    while let Some(char) = chars.next() {
+        if char == '§' {
+            chars.next();
+        } else {
+            result.push(char);
+        }
+    }
+
+    result
+}
+
+/// Parses `host` or `host:port` of `--address`.
+pub fn parse_address(address: &str) -> std::result::Result<(String, Option<u16>), String> {
+    match address.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) if port > 0 && !host.is_empty() => Ok((host.to_string(), Some(port))),
+            _ => Err(format!("`{port}` is not a valid port")),
+        },
+        None => Ok((address.to_string(), None)),
+    }
+}
+
+pub async fn rcon(matches: &ArgMatches) -> Result {
+    let command = matches
+        .get_many::<String>("command")
+        .map(|command| command.cloned().collect::<Vec<String>>().join(" "));
+    let address = matches.get_one::<(String, Option<u16>)>("address");
+    let password = matches.get_one::<String>("password");
+
+    let mut rcon_config = config::Config::new_optional(".mcvcli.json")
+        .map(|config| config.rcon)
+        .unwrap_or_default();
+
+    if let Some((host, port)) = address {
+        rcon_config.host = host.clone();
+        rcon_config.port = Some(port.or(rcon_config.port).unwrap_or(25575));
+    }
+    if let Some(password) = password {
+        rcon_config.password = Some(password.to_string());
+    }
+
+    let address = match Address::resolve(&rcon_config) {
+        Ok(address) => address,
+        Err(err) => {
+            return Err(Error::InvalidConfig(err));
+        }
+    };
+
+    let display = format!("{}:{}", address.host, address.port);
+
+    if command.is_none() {
+        println!(
+            "{} {}{}",
+            "connecting to".bright_black(),
+            display.cyan(),
+            "...".bright_black()
+        );
+    }
+
+    let mut rcon = match Rcon::connect(&address).await {
+        Ok(rcon) => rcon,
+        Err(err) => {
+            println!(
+                "{} {}{} {}",
+                "connecting to".bright_black(),
+                display.cyan(),
+                "...".bright_black(),
+                "FAILED".red().bold()
+            );
+            return Err(Error::io(err));
+        }
+    };
+
+    if let Some(command) = command {
+        return match rcon.command(&command).await {
+            Ok(output) => {
+                println!("{}", strip_formatting(&output));
+                Ok(())
+            }
+            Err(err) => Err(Error::io(err)),
+        };
+    }
+
+    println!(
+        "{} {}{} {}",
+        "connecting to".bright_black(),
+        display.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+    println!("{}", "type a command, press ctrl+d to exit".bright_black());
+    println!();
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+    loop {
+        print!("{} ", ">".cyan());
+        std::io::stdout().flush().unwrap_or_default();
+
+        let command = match lines.next_line().await {
+            Ok(Some(command)) => command,
+            Ok(None) | Err(_) => break,
+        };
+
+        if command.trim().is_empty() {
+            continue;
+        }
+
+        match rcon.command(command.trim()).await {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => println!("{}", strip_formatting(&output)),
+            Err(err) => {
+                return Err(Error::io(err));
+            }
+        }
+    }
+
+    println!();
+
+    Ok(())
+}
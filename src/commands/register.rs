@@ -0,0 +1,62 @@
+use crate::error::{Error, Result};
+use crate::registry::Registry;
+
+use clap::ArgMatches;
+use colored::Colorize;
+use std::path::Path;
+
+pub async fn register(matches: &ArgMatches) -> Result {
+    if !Path::new(".mcvcli.json").exists() {
+        return Err(Error::ConfigMissing);
+    }
+
+    let mut registry = Registry::load();
+    let directory = std::env::current_dir()?;
+
+    if let Some(server) = registry.find(&directory) {
+        return Err(Error::other(format!(
+            "server is already registered as `{}`",
+            server.name
+        )));
+    }
+
+    let name = match matches.get_one::<String>("name") {
+        Some(name) => name.clone(),
+        None => registry.free_name(&directory),
+    };
+
+    if registry.get(&name).is_some() {
+        return Err(Error::other(format!(
+            "server `{name}` is already registered"
+        )));
+    }
+
+    println!(
+        "{} {} {}",
+        "registering server".bright_black(),
+        name.cyan(),
+        "...".bright_black()
+    );
+
+    registry.add(&name, &directory);
+    if let Err(err) = registry.save() {
+        println!(
+            "{} {} {} {}",
+            "registering server".bright_black(),
+            name.cyan(),
+            "...".bright_black(),
+            "FAILED".red().bold()
+        );
+        return Err(Error::io(err));
+    }
+
+    println!(
+        "{} {} {} {}",
+        "registering server".bright_black(),
+        name.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    Ok(())
+}
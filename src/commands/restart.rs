@@ -0,0 +1,110 @@
+use crate::error::{Error, Result};
+use crate::supervisor::{
+    self,
+    client::Client,
+    protocol::{Event, Request, Response},
+};
+use crate::{commands::stop, config, detached};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use std::time::Duration;
+
+pub async fn restart(matches: &ArgMatches) -> Result {
+    let countdown = matches.get_one::<Duration>("countdown").copied();
+    let abort = *matches.get_one::<bool>("abort").expect("required");
+    let ready_timeout = *matches.get_one::<u64>("ready_timeout").expect("required");
+    let config = config::Config::new(".mcvcli.json", false)?;
+
+    if !detached::running(&config) {
+        return Err(Error::not_running());
+    }
+
+    let mut client = match Client::connect(config.identifier.as_deref().unwrap_or_default()).await {
+        Ok(client) => client,
+        Err(_) => return Err(Error::io("unable to connect to the server supervisor")),
+    };
+
+    if abort {
+        return stop::abort(&mut client).await;
+    }
+
+    if let Some(countdown) = countdown {
+        println!(
+            "{} {}",
+            format!(
+                "restarting server in {}, cancel with",
+                supervisor::describe(countdown)
+            )
+            .bright_black(),
+            "mcvcli restart --abort".cyan()
+        );
+    } else {
+        println!("{}", "restarting server ...".bright_black());
+    }
+
+    client.send(&Request::Subscribe { tail: 0 }).await?;
+    client
+        .send(&Request::Restart {
+            countdown: countdown.unwrap_or_default().as_secs(),
+        })
+        .await?;
+
+    // the countdown and the stop have their own limits, the timeout starts once the old
+    // process has exited
+    let mut deadline = None;
+
+    loop {
+        let response = match deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, client.recv()).await {
+                Ok(response) => response,
+                Err(_) => {
+                    println!();
+                    return Err(Error::TimedOut(format!(
+                        "server did not become ready within {ready_timeout}s after restarting"
+                    )));
+                }
+            },
+            None => client.recv().await,
+        };
+
+        match response {
+            Ok(Some(Response::Event {
+                event: Event::Line { line },
+            })) => println!("{line}"),
+            Ok(Some(Response::Event {
+                event: Event::ProcessExited { .. },
+            })) => {
+                deadline = Some(tokio::time::Instant::now() + Duration::from_secs(ready_timeout))
+            }
+            Ok(Some(Response::Event {
+                event: Event::Ready { .. },
+            })) if deadline.is_some() => break,
+            Ok(Some(Response::Event {
+                event: Event::Exited { code },
+            })) => {
+                println!();
+                return Err(Error::other(format!(
+                    "server has stopped with code {}",
+                    code.unwrap_or(0)
+                )));
+            }
+            Ok(Some(Response::Event {
+                event: Event::Aborted,
+            })) => return Err(Error::Aborted),
+            Ok(Some(Response::Error { message })) => return Err(Error::Other(message)),
+            Ok(Some(_)) => continue,
+            Ok(None) => return Err(Error::io("the server supervisor closed the connection")),
+            Err(err) => return Err(Error::io(err)),
+        }
+    }
+
+    println!();
+    println!(
+        "{} {}",
+        "restarting server ...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::config::{self, ScheduleAction, ScheduleEntry};
+use crate::error::{Error, Result};
+use crate::supervisor::schedule;
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn add(matches: &ArgMatches) -> Result {
+    let name = matches.get_one::<String>("name").expect("required");
+    let cron = matches.get_one::<String>("cron").expect("required");
+    let command = matches.get_one::<String>("command");
+    let restart = *matches.get_one::<bool>("restart").expect("required");
+    let backup = *matches.get_one::<bool>("backup").expect("required");
+    let warnings = matches
+        .get_many::<String>("warnings")
+        .map(|warnings| warnings.cloned().collect::<Vec<String>>())
+        .unwrap_or_default();
+    let keep = matches.get_one::<usize>("keep").copied();
+
+    let mut config = config::Config::new(".mcvcli.json", false)?;
+
+    if config.schedule.iter().any(|entry| entry.name == *name) {
+        return Err(Error::other(format!(
+            "scheduled task `{name}` already exists"
+        )));
+    }
+
+    if let Err(err) = schedule::parse(cron) {
+        return Err(Error::other(format!(
+            "cron expression `{cron}` is invalid: {err}"
+        )));
+    }
+
+    if let Some(warning) = warnings
+        .iter()
+        .find(|warning| humantime::parse_duration(warning).is_err())
+    {
+        return Err(Error::other(format!(
+            "warning `{warning}` is not a valid duration (e.g. 5m, 30s)"
+        )));
+    }
+
+    let action = match (command, restart, backup) {
+        (Some(command), false, false) => ScheduleAction::Command {
+            command: command.to_string(),
+        },
+        (None, true, false) => ScheduleAction::Restart { warnings },
+        (None, false, true) => ScheduleAction::Backup { keep },
+        _ => {
+            return Err(Error::other(
+                "use exactly one of `--command`, `--restart` or `--backup`",
+            ));
+        }
+    };
+
+    let entry = ScheduleEntry {
+        name: name.to_string(),
+        cron: cron.to_string(),
+        action,
+    };
+    if let Err(err) = entry.validate() {
+        return Err(Error::Other(err));
+    }
+
+    println!(
+        "{} {} {}",
+        "adding scheduled task".bright_black(),
+        name.cyan(),
+        "...".bright_black()
+    );
+
+    config.schedule.push(entry);
+    config.save()?;
+
+    println!(
+        "{} {} {} {}",
+        "adding scheduled task".bright_black(),
+        name.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    Ok(())
+}
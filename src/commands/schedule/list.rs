@@ -0,0 +1,49 @@
+use crate::config::{self, ScheduleAction};
+use crate::error::{Error, Result};
+use crate::supervisor::schedule;
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn list(_matches: &ArgMatches) -> Result {
+    let config = config::Config::new(".mcvcli.json", false)?;
+
+    if config.schedule.is_empty() {
+        return Err(Error::not_found(
+            "no scheduled tasks, use `mcvcli schedule add`",
+        ));
+    }
+
+    for entry in config.schedule.iter() {
+        println!();
+        println!("{}", entry.name.cyan().bold().underline());
+
+        println!("  {} {}", "cron:    ".bright_black(), entry.cron.cyan());
+        println!(
+            "  {} {}",
+            "action:  ".bright_black(),
+            match &entry.action {
+                ScheduleAction::Command { command } => format!("command ({command})"),
+                ScheduleAction::Restart { warnings } if warnings.is_empty() => {
+                    "restart".to_string()
+                }
+                ScheduleAction::Restart { warnings } => {
+                    format!("restart (warnings: {})", warnings.join(", "))
+                }
+                ScheduleAction::Backup { keep: Some(keep) } => format!("backup (keep {keep})"),
+                ScheduleAction::Backup { keep: None } => "backup".to_string(),
+            }
+            .cyan()
+        );
+        println!(
+            "  {} {}",
+            "next run:".bright_black(),
+            match schedule::next(entry) {
+                Some(next) => next.format("%Y-%m-%d %H:%M:%S").to_string().cyan(),
+                None => "invalid cron expression".red(),
+            }
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::config;
+use crate::error::{Error, Result};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use dialoguer::{FuzzySelect, theme::ColorfulTheme};
+
+pub async fn remove(matches: &ArgMatches) -> Result {
+    let name = matches.get_one::<String>("name");
+    let mut config = config::Config::new(".mcvcli.json", false)?;
+
+    let list = config
+        .schedule
+        .iter()
+        .map(|entry| entry.name.clone())
+        .collect::<Vec<String>>();
+
+    let name = if let Some(name) = name {
+        name
+    } else {
+        if list.is_empty() {
+            return Err(Error::not_found("no scheduled tasks to remove"));
+        }
+
+        let name = FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select scheduled task to remove")
+            .items(&list)
+            .default(0)
+            .max_length(5)
+            .interact()?;
+
+        &list[name]
+    };
+
+    if !list.contains(name) {
+        return Err(Error::not_found(format!(
+            "scheduled task `{name}` does not exist"
+        )));
+    }
+
+    println!(
+        "{} {} {}",
+        "removing scheduled task".bright_black(),
+        name.cyan(),
+        "...".bright_black()
+    );
+
+    config.schedule.retain(|entry| entry.name != *name);
+    config.save()?;
+
+    println!(
+        "{} {} {} {}",
+        "removing scheduled task".bright_black(),
+        name.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::error::{Error, Result};
+use crate::tokens::Tokens;
+use crate::{config, http};
+
+use clap::ArgMatches;
+use colored::Colorize;
+use std::net::SocketAddr;
+
+pub async fn serve(matches: &ArgMatches) -> Result {
+    let listen = *matches.get_one::<SocketAddr>("listen").expect("required");
+    config::Config::new(".mcvcli.json", false)?;
+
+    if Tokens::load().tokens.is_empty() {
+        return Err(Error::not_found(
+            "no tokens created, the api would reject every request. use `mcvcli token create <name>`",
+        ));
+    }
+
+    println!(
+        "{} {} {}",
+        "listening on".bright_black(),
+        listen.to_string().cyan(),
+        "...".bright_black()
+    );
+
+    let listener = match tokio::net::TcpListener::bind(listen).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            println!(
+                "{} {} {} {}",
+                "listening on".bright_black(),
+                listen.to_string().cyan(),
+                "...".bright_black(),
+                "FAILED".red().bold()
+            );
+            return Err(Error::io(err));
+        }
+    };
+
+    println!(
+        "{} {} {} {}",
+        "listening on".bright_black(),
+        listen.to_string().cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    if !listen.ip().is_loopback() {
+        println!(
+            "{}",
+            "the api is served without tls, put it behind a reverse proxy when exposing it."
+                .yellow()
+        );
+    }
+
+    tokio::select! {
+        result = axum::serve(listener, http::router()) => {
+            if let Err(err) = result {
+                return Err(Error::io(err));
+            }
+        }
+        _ = tokio::signal::ctrl_c() => {}
+    }
+
+    println!("{}", "api stopped".bright_black());
+
+    Ok(())
+}
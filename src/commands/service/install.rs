@@ -0,0 +1,172 @@
+use crate::error::{Error, Result};
+use crate::{config, service};
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+/// The user a system unit runs the server as, the user that invoked sudo by default.
+fn user(user: Option<&String>) -> Result<String> {
+    if let Some(user) = user {
+        return Ok(user.clone());
+    }
+
+    let user = std::env::var("SUDO_USER")
+        .ok()
+        .filter(|user| !user.is_empty())
+        .or_else(|| std::env::var("USER").ok())
+        .unwrap_or_default();
+
+    if user.is_empty() || user == "root" {
+        return Err(Error::other(
+            "refusing to run the server as root, use `mcvcli service install --system --user <user>`",
+        ));
+    }
+
+    Ok(user)
+}
+
+pub async fn install(matches: &ArgMatches) -> Result {
+    let config = config::Config::new(".mcvcli.json", false)?;
+    let system = *matches.get_one::<bool>("system").expect("required");
+    let eula = *matches.get_one::<bool>("eula").expect("required");
+    let timeout = *matches.get_one::<u64>("timeout").expect("required");
+    let output = matches.get_one::<String>("output");
+    let user = if system {
+        Some(user(matches.get_one::<String>("user"))?)
+    } else {
+        None
+    };
+
+    let directory = std::env::current_dir()?;
+    let name = matches
+        .get_one::<String>("name")
+        .cloned()
+        .unwrap_or_else(|| service::name(&directory));
+
+    let eula_accepted = std::fs::read_to_string("eula.txt")
+        .unwrap_or_default()
+        .contains("eula=true");
+
+    // the service cannot ask for the eula when starting
+    if !eula_accepted {
+        if !eula {
+            return Err(Error::other(
+                "the Minecraft EULA has not been accepted yet, use `mcvcli service install --eula`",
+            ));
+        }
+
+        std::fs::write("eula.txt", "eula=true\n")?;
+    }
+
+    let unit = service::render(
+        &config,
+        &directory,
+        &std::env::current_exe()?,
+        timeout,
+        system,
+        user.as_deref(),
+    );
+
+    match output.map(|output| output.as_str()) {
+        Some("-") => {
+            print!("{unit}");
+            return Ok(());
+        }
+        Some(output) => {
+            println!(
+                "{} {} {}",
+                "writing unit to".bright_black(),
+                output.cyan(),
+                "...".bright_black()
+            );
+
+            if let Err(err) = std::fs::write(output, &unit) {
+                println!(
+                    "{} {} {} {}",
+                    "writing unit to".bright_black(),
+                    output.cyan(),
+                    "...".bright_black(),
+                    "FAILED".red().bold()
+                );
+                return Err(Error::io(err));
+            }
+
+            println!(
+                "{} {} {} {}",
+                "writing unit to".bright_black(),
+                output.cyan(),
+                "...".bright_black(),
+                "DONE".green().bold()
+            );
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let path = service::path(&name, system);
+    if path.exists() {
+        return Err(Error::other(format!(
+            "service `{name}` is already installed, uninstall it first"
+        )));
+    }
+
+    println!(
+        "{} {} {}",
+        "installing service".bright_black(),
+        name.cyan(),
+        "...".bright_black()
+    );
+
+    let written = std::fs::create_dir_all(path.parent().unwrap())
+        .and_then(|_| std::fs::write(&path, &unit))
+        .map_err(|err| format!("unable to write {}: {err}", path.display()));
+
+    if let Err(err) = written {
+        println!(
+            "{} {} {} {}",
+            "installing service".bright_black(),
+            name.cyan(),
+            "...".bright_black(),
+            "FAILED".red().bold()
+        );
+        return Err(Error::io(err));
+    }
+
+    println!(
+        "{} {} {} {}",
+        "installing service".bright_black(),
+        name.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    if let Err(err) = service::systemctl(system, &["daemon-reload"]) {
+        println!(
+            "{} {}",
+            "unable to reload systemd, run daemon-reload yourself:".yellow(),
+            err.yellow()
+        );
+    }
+
+    let systemctl = if system {
+        "systemctl"
+    } else {
+        "systemctl --user"
+    };
+
+    println!();
+    println!(
+        "{} {}",
+        "start the server on boot with".bright_black(),
+        format!("{systemctl} enable --now {name}").cyan()
+    );
+    if !system {
+        println!(
+            "{} {}",
+            "keep it running after logging out with".bright_black(),
+            "loginctl enable-linger".cyan()
+        );
+    }
+
+    Ok(())
+}
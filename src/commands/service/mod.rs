@@ -0,0 +1,3 @@
+pub mod install;
+pub mod status;
+pub mod uninstall;
@@ -0,0 +1,47 @@
+use crate::error::{Error, Result};
+use crate::service;
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn status(matches: &ArgMatches) -> Result {
+    let system = *matches.get_one::<bool>("system").expect("required");
+    let name = matches
+        .get_one::<String>("name")
+        .cloned()
+        .unwrap_or_else(|| service::name(&std::env::current_dir().unwrap_or_default()));
+
+    let path = service::path(&name, system);
+    let installed = path.exists();
+
+    println!("{}", name.cyan().bold().underline());
+    println!(
+        "  {} {}",
+        "unit file:".bright_black(),
+        path.display().to_string().cyan()
+    );
+    println!(
+        "  {} {}",
+        "installed:".bright_black(),
+        if installed { "yes".green() } else { "no".red() }
+    );
+
+    if !installed {
+        return Err(Error::not_found(format!(
+            "service `{name}` is not installed"
+        )));
+    }
+
+    // both exit non-zero for disabled or inactive units but still print the state
+    for (label, command) in [("enabled:  ", "is-enabled"), ("active:   ", "is-active")] {
+        let state = match service::systemctl(system, &[command, &name]) {
+            Ok(state) => state.green(),
+            Err(state) if state.is_empty() => "unknown".red(),
+            Err(state) => state.lines().next().unwrap_or_default().red(),
+        };
+
+        println!("  {} {}", label.bright_black(), state);
+    }
+
+    Ok(())
+}
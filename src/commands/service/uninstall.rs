@@ -0,0 +1,62 @@
+use crate::error::{Error, Result};
+use crate::service;
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn uninstall(matches: &ArgMatches) -> Result {
+    let system = *matches.get_one::<bool>("system").expect("required");
+    let name = match matches.get_one::<String>("name") {
+        Some(name) => name.clone(),
+        None => service::name(&std::env::current_dir()?),
+    };
+
+    let path = service::path(&name, system);
+    if !path.exists() {
+        return Err(Error::not_found(format!(
+            "service `{name}` is not installed"
+        )));
+    }
+
+    println!(
+        "{} {} {}",
+        "uninstalling service".bright_black(),
+        name.cyan(),
+        "...".bright_black()
+    );
+
+    // the unit might never have been enabled or started
+    service::systemctl(system, &["disable", "--now", &name]).unwrap_or_default();
+
+    let removed = std::fs::remove_file(&path)
+        .map_err(|err| format!("unable to remove {}: {err}", path.display()));
+
+    if let Err(err) = removed {
+        println!(
+            "{} {} {} {}",
+            "uninstalling service".bright_black(),
+            name.cyan(),
+            "...".bright_black(),
+            "FAILED".red().bold()
+        );
+        return Err(Error::io(err));
+    }
+
+    println!(
+        "{} {} {} {}",
+        "uninstalling service".bright_black(),
+        name.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    if let Err(err) = service::systemctl(system, &["daemon-reload"]) {
+        println!(
+            "{} {}",
+            "unable to reload systemd, run daemon-reload yourself:".yellow(),
+            err.yellow()
+        );
+    }
+
+    Ok(())
+}
@@ -1,19 +1,34 @@
 use crate::api::{self, Progress};
-use crate::{config, detached, java};
+use crate::error::{Error, Result};
+use crate::hooks::{self, Hook};
+use crate::supervisor::{
+    self, Supervisor,
+    client::Client,
+    protocol::{Event, Request, Response, State},
+};
+use crate::{config, dashboard, detached, java, preflight};
 
 use clap::ArgMatches;
 use colored::Colorize;
 use dialoguer::{Confirm, theme::ColorfulTheme};
 use human_bytes::human_bytes;
 use rand::{Rng, distr::Alphanumeric};
-use std::{fs::File, io::Read, io::Write, path::Path, sync::Arc};
-use tokio::{io::AsyncWriteExt, process::Command, sync::Mutex};
-
-pub async fn start(matches: &ArgMatches) -> i32 {
-    let mut config = config::Config::new(".mcvcli.json", false);
+use std::{fs::File, io::Write, os::unix::process::CommandExt, path::Path, sync::Arc};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    sync::broadcast,
+    task::JoinHandle,
+};
+
+pub async fn start(matches: &ArgMatches) -> Result {
+    let config = config::Config::new(".mcvcli.json", false)?;
     let auto_agree_eula = *matches.get_one::<bool>("eula").expect("required");
     let detached = *matches.get_one::<bool>("detached").expect("required");
     let timeout = *matches.get_one::<u64>("timeout").expect("required");
+    let wait_ready = *matches.get_one::<bool>("wait_ready").expect("required");
+    let ready_timeout = *matches.get_one::<u64>("ready_timeout").expect("required");
+    let force = *matches.get_one::<bool>("force").expect("required");
+    let dashboard = *matches.get_one::<bool>("dashboard").expect("required");
 
     let eula_accepted = std::fs::read_to_string("eula.txt")
         .unwrap_or_default()
@@ -24,27 +39,38 @@ pub async fn start(matches: &ArgMatches) -> i32 {
             let accept_eula = Confirm::with_theme(&ColorfulTheme::default())
                 .with_prompt("Do you accept the Minecraft EULA? (https://minecraft.net/eula)")
                 .default(false)
-                .interact()
-                .unwrap();
+                .interact()?;
 
             if !accept_eula {
-                return 1;
+                return Err(Error::Aborted);
             }
         }
 
-        std::fs::write("eula.txt", "eula=true\n").unwrap();
+        std::fs::write("eula.txt", "eula=true\n")?;
     }
 
-    if detached::status(config.pid) {
-        println!(
-            "{} {}",
-            "server is already running, use".red(),
-            "mcvcli attach".cyan()
-        );
-        return 1;
+    if detached::running(&config) {
+        return Err(Error::Running(
+            "server is already running, use `mcvcli attach`".to_string(),
+        ));
     }
 
-    let [binary, java_home] = java::binary(config.java_version).await;
+    let problems = preflight::check(&config);
+    if !problems.is_empty() {
+        for problem in &problems {
+            println!("{}", problem.red());
+        }
+
+        if !force {
+            return Err(Error::other(
+                "fix the problems above or use `mcvcli start --force` to start anyway",
+            ));
+        }
+
+        println!("{}", "starting anyway (--force)".yellow());
+    }
+
+    let [binary, java_home] = java::binary(config.java_version).await?;
     let command = format!(
         "{} {} -Xmx{}M -jar {} nogui {}",
         binary,
@@ -61,11 +87,10 @@ pub async fn start(matches: &ArgMatches) -> i32 {
             let mut req = api::CLIENT
                 .get("https://s3.mcjars.app/forge/ForgeServerJAR.jar")
                 .send()
-                .await
-                .unwrap();
-            let mut file = File::create(&config.jar_file).unwrap();
+                .await?;
+            let mut file = File::create(&config.jar_file)?;
 
-            let mut progress = Progress::new(req.content_length().unwrap() as usize);
+            let mut progress = Progress::new(req.content_length().unwrap_or_default() as usize);
             progress.spinner(|progress, spinner| {
                 format!(
                     "\r {} {} {}/{} ({}%)      ",
@@ -83,12 +108,12 @@ pub async fn start(matches: &ArgMatches) -> i32 {
                 )
             });
 
-            while let Some(chunk) = req.chunk().await.unwrap() {
-                file.write_all(&chunk).unwrap();
+            while let Some(chunk) = req.chunk().await? {
+                file.write_all(&chunk)?;
                 progress.incr(chunk.len());
             }
 
-            file.sync_all().unwrap();
+            file.sync_all()?;
             progress.finish();
             println!();
 
@@ -103,11 +128,10 @@ pub async fn start(matches: &ArgMatches) -> i32 {
             let mut req = api::CLIENT
                 .get("https://s3.mcjars.app/neoforge/NeoForgeServerJAR.jar")
                 .send()
-                .await
-                .unwrap();
-            let mut file = File::create(&config.jar_file).unwrap();
+                .await?;
+            let mut file = File::create(&config.jar_file)?;
 
-            let mut progress = Progress::new(req.content_length().unwrap() as usize);
+            let mut progress = Progress::new(req.content_length().unwrap_or_default() as usize);
             progress.spinner(|progress, spinner| {
                 format!(
                     "\r {} {} {}/{} ({}%)      ",
@@ -127,12 +151,12 @@ pub async fn start(matches: &ArgMatches) -> i32 {
                 )
             });
 
-            while let Ok(Some(chunk)) = req.chunk().await {
-                file.write_all(&chunk).unwrap();
+            while let Some(chunk) = req.chunk().await? {
+                file.write_all(&chunk)?;
                 progress.incr(chunk.len());
             }
 
-            file.sync_all().unwrap();
+            file.sync_all()?;
 
             progress.finish();
             println!();
@@ -145,119 +169,68 @@ pub async fn start(matches: &ArgMatches) -> i32 {
                 "DONE".green().bold().italic()
             );
         } else {
-            println!("{}", "no server jar found".red());
-            return 1;
+            return Err(Error::not_found("no server jar found"));
         }
     }
 
+    if let Err(err) = config.env_file() {
+        println!("{}", format!("{err}, starting without it").yellow());
+    }
+
+    if let Err(err) = hooks::run(Hook::PreStart, &config, &[]).await {
+        return Err(Error::Other(err));
+    }
+
     println!();
     println!("{}", "starting the minecraft server...".yellow());
     println!("{command}");
 
-    if !detached {
-        let child = Arc::new(Mutex::new({
-            let mut command = Command::new(binary);
-
-            command.args(config.extra_flags);
-            command.arg(format!("-Xmx{}M", config.ram_mb));
-            command.arg("-jar");
-            command.arg(config.jar_file);
-            command.arg("nogui");
-            command.args(config.extra_args);
-            command.env("JAVA_HOME", java_home);
-            command.stdin(std::process::Stdio::piped());
-            command.stdout(std::process::Stdio::inherit());
-            command.stderr(std::process::Stdio::inherit());
-            command.kill_on_drop(true);
-
-            #[cfg(unix)]
-            command.process_group(0);
-
-            command.spawn().unwrap()
-        }));
-
-        let kill = Arc::new(Mutex::new(None));
-        tokio::spawn({
-            let child = Arc::clone(&child);
-            let kill = Arc::clone(&kill);
-            let stop_command = config.stop_command.clone();
-
-            async move {
-                tokio::signal::ctrl_c().await.unwrap();
-
-                println!();
-                println!();
-                println!(
-                    "{}",
-                    format!("stopping server ({timeout}s before being killed) ...").bright_black()
-                );
-                println!();
-
-                if let Some(stdin) = child.lock().await.stdin.as_mut() {
-                    stdin
-                        .write_all((stop_command + "\n").as_bytes())
-                        .await
-                        .unwrap();
-                }
-
-                let child = Arc::clone(&child);
-                *kill.lock().await = Some(tokio::spawn(async move {
-                    tokio::time::sleep(tokio::time::Duration::from_secs(timeout)).await;
-
-                    println!(
-                        "{}",
-                        "server is taking too long to stop, killing it ...".bright_black()
-                    );
+    let identifier: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(7)
+        .map(char::from)
+        .collect();
 
-                    child.lock().await.kill().await.unwrap();
-
-                    println!(
-                        "{} {}",
-                        "server is taking too long to stop, killing it ...".bright_black(),
-                        "DONE".green().bold()
-                    );
-                }));
-            }
-        });
-
-        tokio::spawn({
-            let child = Arc::clone(&child);
-            let mut stdin = std::io::stdin();
-
-            async move {
-                let mut buffer = [0; 1024];
-
-                loop {
-                    match stdin.read(&mut buffer) {
-                        Ok(0) => break,
-                        Ok(n) => {
-                            let mut child = child.lock().await;
-                            if let Some(stdin) = child.stdin.as_mut() {
-                                stdin.write_all(&buffer[..n]).await.unwrap();
-                            }
-                        }
-                        Err(_) => break,
+    if !detached {
+        let launch = supervisor::Launch { binary, java_home };
+        let supervisor = Supervisor::new(&config, identifier.clone(), timeout);
+        let listener = supervisor.listen()?;
+
+        let output = if dashboard {
+            tokio::spawn({
+                let supervisor = Arc::clone(&supervisor);
+                let identifier = identifier.clone();
+
+                async move {
+                    let mode = dashboard::Mode::Foreground { timeout };
+                    let result = match config::Config::new(".mcvcli.json", false) {
+                        Ok(config) => dashboard::run(&config, &identifier, mode)
+                            .await
+                            .map_err(Error::io),
+                        Err(err) => Err(err),
+                    };
+
+                    if let Err(err) = result {
+                        println!(
+                            "{} {}",
+                            "unable to open the dashboard:".red(),
+                            err.to_string().red()
+                        );
                     }
-                }
-            }
-        });
 
-        let code = {
-            loop {
-                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-
-                let mut child = child.lock().await;
-                match child.try_wait() {
-                    Ok(Some(code)) => break code,
-                    Ok(None) => continue,
-                    Err(_) => break Default::default(),
+                    // nothing is left to control the server with
+                    supervisor.stop(timeout).await.unwrap_or_default();
                 }
-            }
+            })
+        } else {
+            self::console(&supervisor, timeout)
         };
 
-        if let Some(kill) = kill.lock().await.take() {
-            kill.abort();
-        }
+        let code = supervisor.run(&launch).await;
+
+        output.await.unwrap_or_default();
+        listener.abort();
+        supervisor.shutdown();
 
         println!();
         println!(
@@ -266,50 +239,180 @@ pub async fn start(matches: &ArgMatches) -> i32 {
             "DONE".green().bold()
         );
 
-        println!(
-            "{} {}",
-            "server has stopped with code".red(),
-            code.code().unwrap_or(0)
-        );
+        let code = code?.unwrap_or(0);
+        println!("{} {}", "server has stopped with code".red(), code);
+
+        // lets service managers tell crashes from regular stops
+        return match code {
+            0 => Ok(()),
+            code => Err(Error::Exited(code)),
+        };
     } else {
         if std::env::consts::OS == "windows" {
-            println!(
-                "{}",
-                "detached mode is currently not supported on windows".red()
-            );
-            return 1;
+            return Err(Error::other(
+                "detached mode is currently not supported on windows",
+            ));
         }
 
-        config.identifier = Some(
-            rand::rng()
-                .sample_iter(&Alphanumeric)
-                .take(7)
-                .map(char::from)
-                .collect(),
-        );
+        #[allow(clippy::zombie_processes)]
+        let mut child = std::process::Command::new(std::env::current_exe()?)
+            .arg("supervise")
+            .arg("--identifier")
+            .arg(&identifier)
+            .arg("--java")
+            .arg(&binary)
+            .arg("--java-home")
+            .arg(&java_home)
+            .arg("--timeout")
+            .arg(timeout.to_string())
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .process_group(0)
+            .spawn()?;
+
+        let client = loop {
+            if let Ok(client) = Client::connect(&identifier).await {
+                break client;
+            }
 
-        let [stdin, stdout, stderr] = detached::get_pipes(config.identifier.as_ref().unwrap());
+            if let Ok(Some(_)) = child.try_wait() {
+                return Err(Error::other("server supervisor has exited unexpectedly"));
+            }
 
-        #[allow(clippy::zombie_processes)]
-        let child = std::process::Command::new(binary)
-            .args(&config.extra_flags)
-            .arg(format!("-Xmx{}M", config.ram_mb))
-            .arg("-jar")
-            .arg(&config.jar_file)
-            .arg("nogui")
-            .args(&config.extra_args)
-            .env("JAVA_HOME", java_home)
-            .stdin(File::open(stdin.path()).unwrap())
-            .stdout(File::create(stdout.path()).unwrap())
-            .stderr(File::create(stderr.path()).unwrap())
-            .spawn()
-            .unwrap();
-
-        config.pid = Some(child.id() as usize);
-        config.save();
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        };
 
         println!("{}", "server has started in detached mode".green());
+
+        if wait_ready {
+            return self::wait_ready(client, ready_timeout).await;
+        }
     }
 
-    0
+    Ok(())
+}
+
+/// Waits until the detached server printed its boot message, fails when it exits or
+/// takes longer than `timeout` seconds.
+async fn wait_ready(mut client: Client, timeout: u64) -> Result {
+    println!(
+        "{}",
+        format!("waiting for the server to be ready ({timeout}s before giving up) ...")
+            .bright_black()
+    );
+
+    if client.send(&Request::Subscribe { tail: 0 }).await.is_err() {
+        return Err(Error::io("unable to connect to the server supervisor"));
+    }
+
+    // the server might have become ready before the subscription
+    if let Ok(State {
+        startup_ms: Some(startup_ms),
+        ..
+    }) = client.state().await
+    {
+        return ready(startup_ms);
+    }
+
+    let result = tokio::time::timeout(std::time::Duration::from_secs(timeout), async {
+        loop {
+            match client.recv().await {
+                Ok(Some(Response::Event {
+                    event: Event::Ready { startup_ms },
+                })) => return Ok(startup_ms),
+                Ok(Some(Response::Event {
+                    event: Event::ProcessExited { code } | Event::Exited { code },
+                })) => return Err(code),
+                Ok(Some(_)) => continue,
+                Ok(None) | Err(_) => return Err(None),
+            }
+        }
+    })
+    .await;
+
+    match result {
+        Ok(Ok(startup_ms)) => ready(startup_ms),
+        Ok(Err(code)) => {
+            println!(
+                "{} {}",
+                "server has exited during startup with code".red(),
+                code.map(|code| code.to_string())
+                    .unwrap_or("unknown".to_string())
+                    .red()
+            );
+            println!(
+                "{} {}",
+                "check the console log with".red(),
+                "mcvcli attach --tail 50".cyan()
+            );
+
+            Err(Error::Exited(code.filter(|code| *code != 0).unwrap_or(1)))
+        }
+        Err(_) => Err(Error::TimedOut(format!(
+            "server did not become ready within {timeout}s"
+        ))),
+    }
+}
+
+fn ready(startup_ms: u64) -> Result {
+    println!(
+        "{} {}",
+        "waiting for the server to be ready ...".bright_black(),
+        format!("DONE ({:.1}s)", startup_ms as f64 / 1000.0)
+            .green()
+            .bold()
+    );
+
+    Ok(())
+}
+
+/// Prints the console of the foreground server and forwards stdin and ctrl-c to it.
+fn console(supervisor: &Arc<Supervisor>, timeout: u64) -> JoinHandle<()> {
+    tokio::spawn({
+        let supervisor = Arc::clone(supervisor);
+
+        async move {
+            // a failing pre_stop hook keeps the server running, ctrl-c can be pressed again
+            while tokio::signal::ctrl_c().await.is_ok() {
+                println!();
+                println!();
+                println!(
+                    "{}",
+                    format!("stopping server ({timeout}s before being killed) ...").bright_black()
+                );
+                println!();
+
+                match supervisor.stop(timeout).await {
+                    Ok(()) => break,
+                    Err(err) => println!("{}", err.to_string().red()),
+                }
+            }
+        }
+    });
+
+    tokio::spawn({
+        let supervisor = Arc::clone(supervisor);
+
+        async move {
+            let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+            while let Ok(Some(line)) = lines.next_line().await {
+                supervisor.command(&line).await.unwrap_or_default();
+            }
+        }
+    });
+
+    let mut events = supervisor.subscribe();
+
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(Event::Line { line }) => println!("{line}"),
+                Ok(Event::Exited { .. }) => break,
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
 }
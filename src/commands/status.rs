@@ -1,28 +1,235 @@
+use crate::error::{Error, Result};
+use crate::properties::{self, Properties};
+use crate::supervisor::{
+    client::Client,
+    restart::{self, Restart},
+};
 use crate::{config, detached};
 
 use chrono::DateTime;
 use clap::ArgMatches;
 use colored::Colorize;
 use human_bytes::human_bytes;
+use serde::Serialize;
+use std::{net::TcpStream, time::Duration};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate};
 
-pub async fn status(_matches: &ArgMatches) -> i32 {
-    let config = config::Config::new(".mcvcli.json", false);
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Players {
+    online: i32,
+    max: i32,
+    sample: Vec<String>,
+}
 
-    if !detached::status(config.pid) {
-        println!(
-            "{} {}",
-            "server is not running, use".red(),
-            "mcvcli start --detached".cyan()
-        );
-        return 1;
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Status {
+    running: bool,
+    profile: String,
+    pid: Option<u32>,
+    supervisor_pid: Option<u32>,
+    cpu_usage: Option<f32>,
+    memory: Option<u64>,
+    start_time: Option<i64>,
+    uptime: Option<i64>,
+    ready: bool,
+    startup_ms: Option<u64>,
+    port: u16,
+    port_open: bool,
+    version: Option<String>,
+    players: Option<Players>,
+    tps: Option<[f64; 3]>,
+    mspt: Option<f64>,
+    restarts: Vec<Restart>,
+}
+
+pub async fn status(matches: &ArgMatches) -> Result {
+    let json = *matches.get_one::<bool>("json").expect("required");
+    let sample = *matches.get_one::<Duration>("sample").expect("required");
+
+    let config = config::Config::new(".mcvcli.json", false)?;
+    let properties = Properties::new(properties::FILE).unwrap_or_default();
+
+    let (host, port) = properties.server_address();
+
+    let mut status = Status {
+        running: false,
+        profile: config.profile_name.clone(),
+        pid: None,
+        supervisor_pid: None,
+        cpu_usage: None,
+        memory: None,
+        start_time: None,
+        uptime: None,
+        ready: false,
+        startup_ms: None,
+        port,
+        port_open: false,
+        version: None,
+        players: None,
+        tps: None,
+        mspt: None,
+        restarts: restart::history(),
+    };
+
+    if !detached::running(&config) {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&status).unwrap());
+        } else {
+            print_restarts(&status.restarts);
+        }
+
+        return Err(Error::not_running());
+    }
+
+    if !json {
+        println!("{}", "getting server status ...".bright_black());
     }
 
-    println!("{}", "getting server status ...".bright_black());
+    let mut client = Client::connect(config.identifier.as_deref().unwrap_or_default())
+        .await
+        .ok();
+
+    let state = match client.as_mut() {
+        Some(client) => client.state().await.ok(),
+        None => None,
+    };
+
+    let (state, mut client) = match (state, client) {
+        (Some(state), Some(client)) => (state, client),
+        _ => {
+            if !json {
+                println!(
+                    "{} {}",
+                    "getting server status ...".bright_black(),
+                    "FAILED".red().bold()
+                );
+            }
+            return Err(Error::io("unable to connect to the server supervisor"));
+        }
+    };
+
+    // the supervisor is waiting to restart the server after it has exited
+    let Some(pid) = state.pid else {
+        status.supervisor_pid = Some(state.supervisor_pid);
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&status).unwrap());
+        } else {
+            println!(
+                "{} {}",
+                "getting server status ...".bright_black(),
+                "DONE".green().bold()
+            );
+            println!(
+                "{}",
+                "server has exited, the supervisor is waiting to restart it (cancel with mcvcli stop)"
+                    .yellow()
+            );
+            print_restarts(&status.restarts);
+        }
+
+        return Err(Error::not_running());
+    };
+    let pid = sysinfo::Pid::from(pid as usize);
+
+    let process = tokio::task::spawn_blocking(move || {
+        let mut sys = sysinfo::System::new();
+        let refresh = ProcessRefreshKind::nothing().with_cpu().with_memory();
 
-    let pid = sysinfo::Pid::from(config.pid.unwrap());
-    let sys = sysinfo::System::new_all();
+        sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, refresh);
+        std::thread::sleep(sample.max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL));
+        sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, refresh);
 
-    let process = sys.process(pid).unwrap();
+        sys.process(pid)
+            .map(|process| (process.cpu_usage(), process.memory(), process.start_time()))
+    });
+
+    let ping = tokio::task::spawn_blocking({
+        let host = host.clone();
+
+        move || {
+            let port_open = TcpStream::connect_timeout(
+                &std::net::SocketAddr::new(
+                    host.parse()
+                        .unwrap_or(std::net::IpAddr::from([127, 0, 0, 1])),
+                    port,
+                ),
+                Duration::from_secs(1),
+            )
+            .is_ok();
+
+            let mut server = msp::Conf::create_with_port(&host, port);
+            server.socket_conf.read_time_out = Some(Duration::from_secs(2));
+            server.socket_conf.write_timeout = Some(Duration::from_secs(2));
+
+            (
+                port_open,
+                port_open.then(|| server.get_server_status().ok()).flatten(),
+            )
+        }
+    });
+
+    let performance = client.performance();
+
+    let (process, ping, performance) = tokio::join!(
+        process,
+        tokio::time::timeout(Duration::from_secs(5), ping),
+        performance
+    );
+
+    let (cpu_usage, memory, start_time) = match process.ok().flatten() {
+        Some(process) => process,
+        None => {
+            if !json {
+                println!(
+                    "{} {}",
+                    "getting server status ...".bright_black(),
+                    "FAILED".red().bold()
+                );
+            }
+            return Err(Error::io("unable to read the server process"));
+        }
+    };
+
+    status.running = true;
+    status.pid = Some(pid.as_u32());
+    status.supervisor_pid = Some(state.supervisor_pid);
+    status.cpu_usage = Some(cpu_usage);
+    status.memory = Some(memory);
+    status.start_time = Some(start_time as i64);
+    status.uptime = Some(chrono::Utc::now().timestamp() - start_time as i64);
+    status.ready = state.ready;
+    status.startup_ms = state.startup_ms;
+
+    if let Ok(Ok((port_open, server))) = ping {
+        status.port_open = port_open;
+
+        if let Some(server) = server {
+            status.version = Some(server.version.name);
+            status.players = Some(Players {
+                online: server.players.online,
+                max: server.players.max,
+                sample: server
+                    .players
+                    .sample
+                    .into_iter()
+                    .map(|player| player.name)
+                    .collect(),
+            });
+        }
+    }
+
+    if let Ok(performance) = performance {
+        status.tps = performance.tps;
+        status.mspt = performance.mspt;
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&status).unwrap());
+        return Ok(());
+    }
 
     println!(
         "{} {}",
@@ -36,25 +243,129 @@ pub async fn status(_matches: &ArgMatches) -> i32 {
         config.profile_name.cyan().cyan().bold().underline(),
         pid.as_u32()
     );
+    println!(
+        "  {} {}",
+        "supervisor:  ".bright_black(),
+        format!("pid {}", state.supervisor_pid).cyan()
+    );
+    println!(
+        "  {} {}",
+        "cpu usage:   ".bright_black(),
+        format!("{cpu_usage:.1}%").cyan()
+    );
     println!(
         "  {} {}",
         "memory usage:".bright_black(),
-        human_bytes(process.memory() as f64).cyan()
+        human_bytes(memory as f64).cyan()
     );
 
-    let uptime = chrono::Utc::now().timestamp() - process.start_time() as i64;
+    let uptime = status.uptime.unwrap_or_default();
     println!(
         "  {} {} ({}h {}m {}s)",
         "start time:  ".bright_black(),
-        DateTime::from_timestamp(process.start_time() as i64, 0)
-            .unwrap()
-            .format("%Y-%m-%d %H:%M:%S")
-            .to_string()
+        DateTime::from_timestamp(start_time as i64, 0)
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default()
             .cyan(),
         (uptime / 3600).to_string().cyan(),
         ((uptime % 3600) / 60).to_string().cyan(),
         (uptime % 60).to_string().cyan()
     );
+    println!(
+        "  {} {}",
+        "ready:       ".bright_black(),
+        match status.startup_ms {
+            Some(startup_ms) =>
+                format!("yes (started in {:.1}s)", startup_ms as f64 / 1000.0).green(),
+            None => "no (still starting)".yellow(),
+        }
+    );
+    println!(
+        "  {} {} {}",
+        "port:        ".bright_black(),
+        port.to_string().cyan(),
+        if status.port_open {
+            "(open)".green()
+        } else {
+            "(closed)".red()
+        }
+    );
 
-    0
+    if let Some(version) = &status.version {
+        println!("  {} {}", "version:     ".bright_black(), version.cyan());
+    }
+    if let Some(players) = &status.players {
+        println!(
+            "  {} {}{}",
+            "players:     ".bright_black(),
+            format!("{}/{}", players.online, players.max).cyan(),
+            if players.sample.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", players.sample.join(", "))
+            }
+        );
+    }
+    if let Some(tps) = status.tps {
+        println!(
+            "  {} {} {}",
+            "tps:         ".bright_black(),
+            format!("{:.1}, {:.1}, {:.1}", tps[0], tps[1], tps[2]).cyan(),
+            "(1m, 5m, 15m)".bright_black()
+        );
+    }
+    if let Some(mspt) = status.mspt {
+        println!(
+            "  {} {}",
+            "mspt:        ".bright_black(),
+            format!("{mspt:.1}ms").cyan()
+        );
+    }
+
+    print_restarts(&status.restarts);
+
+    Ok(())
+}
+
+fn print_restarts(history: &[Restart]) {
+    if history.is_empty() {
+        return;
+    }
+
+    println!("  {}", "recent restarts:".bright_black());
+
+    for restart in history.iter().rev().take(5) {
+        println!(
+            "    {} {} {}",
+            DateTime::from_timestamp(restart.time, 0)
+                .unwrap_or_default()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+                .cyan(),
+            format!(
+                "exit code {}",
+                restart
+                    .code
+                    .map(|code| code.to_string())
+                    .unwrap_or("unknown".to_string())
+            )
+            .cyan(),
+            if restart.gave_up {
+                "(gave up, crash loop)".red()
+            } else {
+                format!("(attempt {})", restart.attempt).yellow()
+            }
+        );
+    }
+
+    if let Some(restart) = history.last() {
+        println!(
+            "  {}",
+            "last lines before the latest restart:".bright_black()
+        );
+
+        for line in restart.lines.iter() {
+            println!("    {}", line.bright_black());
+        }
+    }
 }
@@ -1,98 +1,187 @@
-use crate::{config, detached};
+use crate::config::{Config, StopTransport};
+use crate::error::{Error, Result};
+use crate::hooks::{self, Hook};
+use crate::supervisor::{
+    self,
+    client::Client,
+    protocol::{Event, Request, Response},
+};
+use crate::{config, detached, rcon};
 
 use clap::ArgMatches;
 use colored::Colorize;
-use std::io::Write;
+use std::time::Duration;
 
-pub async fn stop(matches: &ArgMatches) -> i32 {
+pub async fn stop(matches: &ArgMatches) -> Result {
     let timeout = *matches.get_one::<u64>("timeout").expect("required");
-    let mut config = config::Config::new(".mcvcli.json", false);
+    let countdown = matches.get_one::<Duration>("countdown").copied();
+    let abort = *matches.get_one::<bool>("abort").expect("required");
+    let config = config::Config::new(".mcvcli.json", false)?;
+
+    if !detached::running(&config)
+        && config.stop_transport == StopTransport::Rcon
+        && countdown.is_none()
+        && !abort
+    {
+        if let Err(err) = hooks::run(Hook::PreStop, &config, &[]).await {
+            return Err(Error::Other(err));
+        }
+
+        stop_rcon(&config).await?;
+
+        // the exit code of a server that is not managed by mcvcli is unknown
+        if let Err(err) = hooks::run(
+            Hook::PostStop,
+            &config,
+            &[("MCVCLI_EXIT_CODE", String::new())],
+        )
+        .await
+        {
+            return Err(Error::Other(err));
+        }
+
+        return Ok(());
+    }
+
+    if !detached::running(&config) {
+        return Err(Error::not_running());
+    }
+
+    let mut client = match Client::connect(config.identifier.as_deref().unwrap_or_default()).await {
+        Ok(client) => client,
+        Err(_) => return Err(Error::io("unable to connect to the server supervisor")),
+    };
+
+    if abort {
+        return self::abort(&mut client).await;
+    }
 
-    if !detached::status(config.pid) {
+    if let Some(countdown) = countdown {
         println!(
             "{} {}",
-            "server is not running, use".red(),
-            "mcvcli start --detached".cyan()
+            format!(
+                "stopping server in {} ({timeout}s before being killed), cancel with",
+                supervisor::describe(countdown)
+            )
+            .bright_black(),
+            "mcvcli stop --abort".cyan()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("stopping server ({timeout}s before being killed) ...").bright_black()
         );
-        return 1;
     }
 
-    let [mut stdin, mut stdout, mut stderr] = detached::get_pipes(&config.identifier.unwrap());
-    let mut threads = Vec::new();
+    client.send(&Request::Subscribe { tail: 0 }).await?;
+    client
+        .send(&Request::Stop {
+            timeout,
+            countdown: countdown.unwrap_or_default().as_secs(),
+        })
+        .await?;
+
+    loop {
+        match client.recv().await {
+            Ok(Some(Response::Event {
+                event: Event::Line { line },
+            })) => println!("{line}"),
+            Ok(Some(Response::Event {
+                event: Event::Exited { .. },
+            })) => break,
+            Ok(Some(Response::Event {
+                event: Event::Aborted,
+            })) => return Err(Error::Aborted),
+            Ok(Some(Response::Error { message })) => return Err(Error::Other(message)),
+            Ok(Some(_)) => continue,
+            Ok(None) | Err(_) => break,
+        }
+    }
 
+    println!();
     println!(
-        "{}",
-        format!("stopping server ({timeout}s before being killed) ...").bright_black()
+        "{} {}",
+        "stopping server ...".bright_black(),
+        "DONE".green().bold()
     );
 
-    threads.push(tokio::spawn(async move {
-        std::io::copy(&mut stdout, &mut std::io::stdout()).unwrap();
-    }));
+    Ok(())
+}
 
-    threads.push(tokio::spawn(async move {
-        std::io::copy(&mut stderr, &mut std::io::stderr()).unwrap();
-    }));
+/// Cancels the stop or restart countdown of the supervisor.
+pub async fn abort(client: &mut Client) -> Result {
+    println!("{}", "aborting countdown ...".bright_black());
 
-    threads.push(tokio::spawn({
-        let stop_command = config.stop_command.clone();
+    let response = match client.send(&Request::Abort).await {
+        Ok(()) => client.recv().await,
+        Err(err) => Err(err),
+    };
 
-        async move {
-            stdin.write_all((stop_command + "\n").as_bytes()).unwrap();
+    match response {
+        Ok(Some(Response::Ok)) => {
+            println!(
+                "{} {}",
+                "aborting countdown ...".bright_black(),
+                "DONE".green().bold()
+            );
 
-            std::io::copy(&mut std::io::stdin(), &mut stdin).unwrap();
+            Ok(())
         }
-    }));
-
-    let kill = tokio::spawn(async move {
-        tokio::time::sleep(tokio::time::Duration::from_secs(timeout)).await;
-
-        if detached::status(config.pid) {
+        Ok(Some(Response::Error { message })) => {
             println!(
-                "{}",
-                "server is taking too long to stop, killing it ...".bright_black()
+                "{} {}",
+                "aborting countdown ...".bright_black(),
+                "FAILED".red().bold()
             );
 
-            let pid = sysinfo::Pid::from(config.pid.unwrap());
-            let sys = sysinfo::System::new_all();
-            let process = sys.process(pid).unwrap();
-
-            process.kill_with(sysinfo::Signal::Kill);
-
+            Err(Error::Other(message))
+        }
+        _ => {
             println!(
                 "{} {}",
-                "server is taking too long to stop, killing it ...".bright_black(),
-                "DONE".green().bold()
+                "aborting countdown ...".bright_black(),
+                "FAILED".red().bold()
             );
+
+            Err(Error::io("unable to connect to the server supervisor"))
         }
-    });
-
-    tokio::spawn(async move {
-        loop {
-            if !detached::status(config.pid) {
-                println!();
-                println!(
-                    "{} {}",
-                    "stopping server ...".bright_black(),
-                    "DONE".green().bold()
-                );
-                break;
-            }
-
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    }
+}
+
+/// Stops a server that is not managed by mcvcli (or on another host) over rcon.
+async fn stop_rcon(config: &Config) -> Result {
+    let address = match rcon::Address::resolve(&config.rcon) {
+        Ok(address) => address,
+        Err(err) => {
+            return Err(Error::InvalidConfig(err));
         }
-    })
-    .await
-    .unwrap();
+    };
 
-    kill.abort();
+    println!(
+        "{} {}{}",
+        "stopping server over rcon".bright_black(),
+        format!("{}:{}", address.host, address.port).cyan(),
+        "...".bright_black()
+    );
 
-    for thread in threads {
-        thread.abort();
+    if let Err(err) = rcon::stop(&address, &config.stop_command).await {
+        println!(
+            "{} {}{} {}",
+            "stopping server over rcon".bright_black(),
+            format!("{}:{}", address.host, address.port).cyan(),
+            "...".bright_black(),
+            "FAILED".red().bold()
+        );
+        return Err(Error::io(err));
     }
 
-    config.pid = None;
-    config.identifier = None;
-    config.save();
+    println!(
+        "{} {}{} {}",
+        "stopping server over rcon".bright_black(),
+        format!("{}:{}", address.host, address.port).cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
 
-    0
+    Ok(())
 }
@@ -0,0 +1,50 @@
+use crate::config;
+use crate::error::{Error, Result};
+use crate::supervisor::{Launch, Supervisor};
+
+use clap::ArgMatches;
+use std::sync::Arc;
+use tokio::signal::unix::{SignalKind, signal};
+
+pub async fn supervise(matches: &ArgMatches) -> Result {
+    let config = config::Config::new(".mcvcli.json", false)?;
+    let identifier = matches.get_one::<String>("identifier").expect("required");
+    let timeout = *matches.get_one::<u64>("timeout").expect("required");
+
+    let launch = Launch {
+        binary: matches
+            .get_one::<String>("java")
+            .expect("required")
+            .to_string(),
+        java_home: matches
+            .get_one::<String>("java_home")
+            .expect("required")
+            .to_string(),
+    };
+
+    let supervisor = Supervisor::new(&config, identifier.to_string(), timeout);
+    let listener = supervisor.listen()?;
+
+    tokio::spawn({
+        let supervisor = Arc::clone(&supervisor);
+
+        async move {
+            let Ok(mut terminate) = signal(SignalKind::terminate()) else {
+                return;
+            };
+            terminate.recv().await;
+
+            supervisor.terminate(timeout).await;
+        }
+    });
+
+    let code = supervisor.run(&launch).await;
+
+    listener.abort();
+    supervisor.shutdown();
+
+    match code? {
+        Some(0) | None => Ok(()),
+        Some(code) => Err(Error::Exited(code)),
+    }
+}
@@ -0,0 +1,49 @@
+use crate::error::{Error, Result};
+use crate::tokens::Tokens;
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn create(matches: &ArgMatches) -> Result {
+    let name = matches.get_one::<String>("name").expect("required");
+    let mut tokens = Tokens::load();
+
+    if tokens.get(name).is_some() {
+        return Err(Error::other(format!("token `{name}` already exists")));
+    }
+
+    println!(
+        "{} {} {}",
+        "creating token".bright_black(),
+        name.cyan(),
+        "...".bright_black()
+    );
+
+    let token = tokens.create(name);
+    if let Err(err) = tokens.save() {
+        println!(
+            "{} {} {} {}",
+            "creating token".bright_black(),
+            name.cyan(),
+            "...".bright_black(),
+            "FAILED".red().bold()
+        );
+        return Err(Error::io(err));
+    }
+
+    println!(
+        "{} {} {} {}",
+        "creating token".bright_black(),
+        name.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+    println!();
+    println!("{}", token.cyan().bold());
+    println!(
+        "{}",
+        "store it somewhere safe, it will not be shown again.".bright_black()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::error::Result;
+use crate::tokens::Tokens;
+
+use chrono::DateTime;
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn list(_matches: &ArgMatches) -> Result {
+    let tokens = Tokens::load();
+
+    if tokens.tokens.is_empty() {
+        println!(
+            "{} {}",
+            "no tokens created, use".bright_black(),
+            "mcvcli token create".cyan()
+        );
+        return Ok(());
+    }
+
+    for token in tokens.tokens.iter() {
+        println!();
+        println!("{}", token.name.cyan().bold().underline());
+        println!(
+            "  {} {}",
+            "created:".bright_black(),
+            DateTime::from_timestamp(token.created, 0)
+                .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                .unwrap_or_default()
+                .cyan()
+        );
+    }
+
+    Ok(())
+}
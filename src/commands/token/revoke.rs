@@ -0,0 +1,42 @@
+use crate::error::{Error, Result};
+use crate::tokens::Tokens;
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn revoke(matches: &ArgMatches) -> Result {
+    let name = matches.get_one::<String>("name").expect("required");
+    let mut tokens = Tokens::load();
+
+    if !tokens.remove(name) {
+        return Err(Error::not_found(format!("token `{name}` does not exist")));
+    }
+
+    println!(
+        "{} {} {}",
+        "revoking token".bright_black(),
+        name.cyan(),
+        "...".bright_black()
+    );
+
+    if let Err(err) = tokens.save() {
+        println!(
+            "{} {} {} {}",
+            "revoking token".bright_black(),
+            name.cyan(),
+            "...".bright_black(),
+            "FAILED".red().bold()
+        );
+        return Err(Error::io(err));
+    }
+
+    println!(
+        "{} {} {} {}",
+        "revoking token".bright_black(),
+        name.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    Ok(())
+}
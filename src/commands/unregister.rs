@@ -0,0 +1,52 @@
+use crate::error::{Error, Result};
+use crate::registry::Registry;
+
+use clap::ArgMatches;
+use colored::Colorize;
+
+pub async fn unregister(matches: &ArgMatches) -> Result {
+    let mut registry = Registry::load();
+
+    let name = match matches.get_one::<String>("name") {
+        Some(name) => name.clone(),
+        None => match registry.find(&std::env::current_dir()?) {
+            Some(server) => server.name.clone(),
+            None => return Err(Error::not_found("this server is not registered")),
+        },
+    };
+
+    if registry.get(&name).is_none() {
+        return Err(Error::not_found(format!(
+            "server `{name}` is not registered"
+        )));
+    }
+
+    println!(
+        "{} {} {}",
+        "unregistering server".bright_black(),
+        name.cyan(),
+        "...".bright_black()
+    );
+
+    registry.servers.retain(|server| server.name != name);
+    if let Err(err) = registry.save() {
+        println!(
+            "{} {} {} {}",
+            "unregistering server".bright_black(),
+            name.cyan(),
+            "...".bright_black(),
+            "FAILED".red().bold()
+        );
+        return Err(Error::io(err));
+    }
+
+    println!(
+        "{} {} {} {}",
+        "unregistering server".bright_black(),
+        name.cyan(),
+        "...".bright_black(),
+        "DONE".green().bold()
+    );
+
+    Ok(())
+}
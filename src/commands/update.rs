@@ -1,3 +1,5 @@
+use crate::error::{Error, Result};
+use crate::hooks::{self, Hook};
 use crate::{api, config, detached, jar, modpack, profiles};
 
 use clap::ArgMatches;
@@ -5,37 +7,28 @@ use colored::Colorize;
 use dialoguer::{FuzzySelect, Select, theme::ColorfulTheme};
 use std::collections::HashMap;
 
-pub async fn update(matches: &ArgMatches) -> i32 {
+pub async fn update(matches: &ArgMatches) -> Result {
     let profile = matches.get_one::<String>("profile");
-    let config = config::Config::new(".mcvcli.json", false);
+    let config = config::Config::new(".mcvcli.json", false)?;
 
-    if detached::status(config.pid) {
-        println!(
-            "{} {}",
-            "server is currently running, use".red(),
-            "mcvcli stop".cyan()
-        );
-        return 1;
+    if detached::running(&config) {
+        return Err(Error::running());
     }
 
-    if profile.is_some() && config.profile_name == *profile.unwrap() {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            profile.unwrap().cyan(),
-            "is currently in use!".red()
-        );
-        return 1;
+    if let Some(profile) = profile
+        && config.profile_name == *profile
+    {
+        return Err(Error::other(format!(
+            "profile `{profile}` is currently in use"
+        )));
     }
 
-    if profile.is_some() && !profiles::list().contains(profile.unwrap()) {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            profile.unwrap().cyan(),
-            "does not exist!".red()
-        );
-        return 1;
+    if let Some(profile) = profile
+        && !profiles::list().contains(profile)
+    {
+        return Err(Error::not_found(format!(
+            "profile `{profile}` does not exist"
+        )));
     }
 
     let directory = if let Some(profile) = profile {
@@ -46,7 +39,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
 
     println!("{}", "checking installed version ...".bright_black());
 
-    let mut config = config::Config::new(&format!("{directory}/.mcvcli.json"), false);
+    let mut config = config::Config::new(&format!("{directory}/.mcvcli.json"), false)?;
     let detected = jar::detect(&directory.clone(), &config).await;
 
     if detected.is_none() {
@@ -55,7 +48,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "checking installed version ...".bright_black(),
             "FAILED".red().bold()
         );
-        return 1;
+        return Err(Error::not_found("installed version could not be detected"));
     }
 
     println!(
@@ -86,9 +79,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
 
     let mut modpack_versions = Vec::new();
     if modpack.is_some() {
-        modpack_versions = api::modrinth::versions(config.modpack_slug.as_ref().unwrap())
-            .await
-            .unwrap();
+        modpack_versions = api::modrinth::versions(config.modpack_slug.as_ref().unwrap()).await?;
 
         if &modpack_versions[0].id != config.modpack_version.as_ref().unwrap() {
             items.push("Update Modpack");
@@ -97,15 +88,14 @@ pub async fn update(matches: &ArgMatches) -> i32 {
 
     if items.is_empty() {
         println!("{}", "everything is up to date!".green());
-        return 0;
+        return Ok(());
     }
 
     let update = Select::with_theme(&ColorfulTheme::default())
         .with_prompt("Update?")
         .default(0)
         .items(&items)
-        .interact()
-        .unwrap();
+        .interact()?;
 
     let update = items[update];
     if update == "Update Version" {
@@ -129,8 +119,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             .default(0)
             .items(&versions)
             .max_length(10)
-            .interact()
-            .unwrap();
+            .interact()?;
         let server_version = &versions[server_version];
 
         println!(
@@ -140,9 +129,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "...".bright_black()
         );
 
-        let builds = api::mcjars::builds(&build.r#type, server_version)
-            .await
-            .unwrap();
+        let builds = api::mcjars::builds(&build.r#type, server_version).await?;
 
         println!(
             "{} {} {} {}",
@@ -157,8 +144,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             .default(0)
             .items(&builds.iter().map(|b| &b.name).collect::<Vec<&String>>())
             .max_length(10)
-            .interact()
-            .unwrap();
+            .interact()?;
 
         let server_build = &builds[server_build];
 
@@ -170,10 +156,10 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "...".bright_black()
         );
 
-        jar::install(server_build, &directory, 1).await.unwrap();
+        jar::install(server_build, &directory, 1).await?;
 
         config.java_version = *versions_java.get(*server_version).unwrap();
-        config.save();
+        config.save()?;
 
         println!(
             "{} {} {} {} {}",
@@ -183,6 +169,17 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "...".bright_black(),
             "DONE".green().bold()
         );
+
+        hooks::run(
+            Hook::PostUpdate,
+            &config,
+            &[
+                ("MCVCLI_VERSION", server_version.to_string()),
+                ("MCVCLI_BUILD_ID", server_build.id.to_string()),
+            ],
+        )
+        .await
+        .unwrap_or_default();
     } else if update == "Update Build" {
         let server_version = build
             .version_id
@@ -194,9 +191,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "...".bright_black()
         );
 
-        let builds = api::mcjars::builds(&build.r#type, &server_version)
-            .await
-            .unwrap();
+        let builds = api::mcjars::builds(&build.r#type, &server_version).await?;
         let builds = builds.iter().rev().collect::<Vec<&api::mcjars::Build>>();
 
         println!(
@@ -215,8 +210,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             .default(0)
             .items(&builds.iter().map(|b| &b.name).collect::<Vec<&String>>())
             .max_length(10)
-            .interact()
-            .unwrap();
+            .interact()?;
 
         let server_build = &builds[server_build];
 
@@ -228,7 +222,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "...".bright_black()
         );
 
-        jar::install(server_build, &directory, 1).await.unwrap();
+        jar::install(server_build, &directory, 1).await?;
 
         println!(
             "{} {} {} {} {}",
@@ -238,6 +232,17 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "...".bright_black(),
             "DONE".green().bold()
         );
+
+        hooks::run(
+            Hook::PostUpdate,
+            &config,
+            &[
+                ("MCVCLI_VERSION", server_version.to_string()),
+                ("MCVCLI_BUILD_ID", server_build.id.to_string()),
+            ],
+        )
+        .await
+        .unwrap_or_default();
     } else if update == "Update Modpack" {
         let modpack = modpack.unwrap();
         let modpack_versions = modpack_versions
@@ -269,8 +274,7 @@ pub async fn update(matches: &ArgMatches) -> i32 {
                     .collect::<Vec<&String>>(),
             )
             .max_length(10)
-            .interact()
-            .unwrap();
+            .interact()?;
         let modpack_version = &versions[modpack_version];
 
         println!(
@@ -280,10 +284,10 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "...".bright_black()
         );
 
-        modpack::install(&directory, modpack_version).await;
+        modpack::install(&directory, modpack_version).await?;
 
         config.modpack_version = Some(modpack_version.id.clone());
-        config.save();
+        config.save()?;
 
         println!(
             "{} {} {} {}",
@@ -296,7 +300,11 @@ pub async fn update(matches: &ArgMatches) -> i32 {
             "...".bright_black(),
             "DONE".green().bold()
         );
+
+        hooks::run(Hook::PostUpdate, &config, &[])
+            .await
+            .unwrap_or_default();
     }
 
-    0
+    Ok(())
 }
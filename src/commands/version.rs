@@ -1,19 +1,18 @@
+use crate::error::{Error, Result};
 use crate::{config, jar, profiles};
 
 use clap::ArgMatches;
 use colored::Colorize;
 
-pub async fn version(matches: &ArgMatches) -> i32 {
+pub async fn version(matches: &ArgMatches) -> Result {
     let profile = matches.get_one::<String>("profile");
 
-    if profile.is_some() && !profiles::list().contains(profile.unwrap()) {
-        println!(
-            "{} {} {}",
-            "profile".red(),
-            profile.unwrap().cyan(),
-            "does not exist!".red()
-        );
-        return 1;
+    if let Some(profile) = profile
+        && !profiles::list().contains(profile)
+    {
+        return Err(Error::not_found(format!(
+            "profile `{profile}` does not exist"
+        )));
     }
 
     let directory = if let Some(profile) = profile {
@@ -24,7 +23,7 @@ pub async fn version(matches: &ArgMatches) -> i32 {
 
     println!("{}", "checking installed version ...".bright_black());
 
-    let config = config::Config::new(&format!("{directory}/.mcvcli.json"), false);
+    let config = config::Config::new(&format!("{directory}/.mcvcli.json"), false)?;
 
     println!(
         "{} {}",
@@ -117,5 +116,5 @@ pub async fn version(matches: &ArgMatches) -> i32 {
         config.java_version.to_string().cyan()
     );
 
-    0
+    Ok(())
 }
@@ -0,0 +1,139 @@
+use serde_json::{Map, Value, json};
+
+/// The schema version of the `.mcvcli.json` files written by this version of mcvcli.
+pub const SCHEMA_VERSION: u32 = 2;
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[n]` upgrades a file from schema version `n + 1` to `n + 2`, files without
+/// `schemaVersion` predate it and are version 1.
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize - 1] = [v1_to_v2];
+
+/// Upgrades the config to `SCHEMA_VERSION` in place, returns the version it had before.
+/// Unknown keys are kept so `mcvcli config validate` can still report them.
+pub fn migrate(value: &mut Value) -> Result<u32, String> {
+    let Value::Object(fields) = value else {
+        return Err("expected a json object".to_string());
+    };
+
+    let version = match fields.get("schemaVersion") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= 1)
+            .ok_or("`schemaVersion` must be a positive number")?,
+    };
+
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "schema version {version} is newer than this mcvcli supports ({SCHEMA_VERSION}), update mcvcli first"
+        ));
+    }
+
+    for migration in &MIGRATIONS[version as usize - 1..] {
+        migration(fields);
+    }
+
+    fields.shift_insert(0, "schemaVersion".to_string(), SCHEMA_VERSION.into());
+
+    Ok(version)
+}
+
+/// Writes out the sections added after the first release, older files relied on serde
+/// defaults for them.
+fn v1_to_v2(fields: &mut Map<String, Value>) {
+    let sections = [
+        ("stopCommand", json!("stop")),
+        ("stopTransport", json!("console")),
+        ("consoleLog", json!({ "maxSizeMB": 10, "maxFiles": 5 })),
+        ("restart", json!({})),
+        ("watchdog", json!({})),
+        ("countdown", json!({})),
+        ("hooks", json!({})),
+        ("env", json!({})),
+        ("envFile", Value::Null),
+        ("limits", json!({})),
+        ("schedule", json!([])),
+        ("rcon", json!({})),
+        ("notifications", json!({})),
+    ];
+
+    for (key, value) in sections {
+        fields.entry(key).or_insert(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use serde::Deserialize;
+
+    fn v1() -> Value {
+        json!({
+            "jarFile": "server.jar",
+            "profileName": "default",
+            "modpackSlug": null,
+            "modpackVersion": null,
+            "ramMB": 2048,
+            "javaVersion": 21,
+            "extraFlags": [],
+            "extraArgs": [],
+            "pid": null,
+            "identifier": null
+        })
+    }
+
+    #[test]
+    fn migrates_v1_to_current() {
+        let mut value = v1();
+
+        assert_eq!(migrate(&mut value), Ok(1));
+        assert_eq!(value["schemaVersion"], json!(SCHEMA_VERSION));
+        assert_eq!(value["stopCommand"], json!("stop"));
+        assert_eq!(
+            value["consoleLog"],
+            json!({ "maxSizeMB": 10, "maxFiles": 5 })
+        );
+        assert!(Config::deserialize(&value).is_ok());
+    }
+
+    #[test]
+    fn keeps_existing_and_unknown_keys() {
+        let mut value = v1();
+        value["stopCommand"] = json!("end");
+        value["consoleLog"] = json!({ "maxFiles": 2 });
+        value["typo"] = json!(true);
+
+        migrate(&mut value).unwrap();
+        assert_eq!(value["stopCommand"], json!("end"));
+        assert_eq!(value["typo"], json!(true));
+
+        let config = Config::deserialize(&value).unwrap();
+        assert_eq!(config.console_log.max_files, 2);
+        assert_eq!(config.console_log.max_size_mb, 10);
+    }
+
+    #[test]
+    fn leaves_current_version_alone() {
+        let mut value = v1();
+        migrate(&mut value).unwrap();
+        let migrated = value.clone();
+
+        assert_eq!(migrate(&mut value), Ok(SCHEMA_VERSION));
+        assert_eq!(value, migrated);
+    }
+
+    #[test]
+    fn rejects_newer_and_invalid_versions() {
+        for version in [json!(SCHEMA_VERSION + 1), json!(0), json!("2"), json!(-1)] {
+            let mut value = v1();
+            value["schemaVersion"] = version;
+
+            assert!(migrate(&mut value).is_err());
+        }
+
+        assert!(migrate(&mut json!([])).is_err());
+    }
+}
@@ -0,0 +1,569 @@
+use crate::error::Error;
+use crate::limits;
+
+use colored::Colorize;
+use indexmap::IndexMap;
+use rand::{Rng, distr::Alphanumeric};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+mod migrations;
+
+pub use migrations::SCHEMA_VERSION;
+
+/// The smallest `ramMB` accepted by `init`, `config --ram` and `config validate`.
+pub const MIN_RAM_MB: u32 = 1024;
+/// The largest `ramMB` accepted by `init`, `config --ram` and `config validate`.
+pub const MAX_RAM_MB: u32 = 49152;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConsoleLogConfig {
+    #[serde(rename = "maxSizeMB")]
+    pub max_size_mb: u32,
+    pub max_files: u8,
+}
+
+impl Default for ConsoleLogConfig {
+    fn default() -> Self {
+        Self {
+            max_size_mb: 10,
+            max_files: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    Never,
+    OnFailure,
+    Always,
+}
+
+impl RestartPolicy {
+    #[inline]
+    pub fn should_restart(self, code: Option<i32>) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => code != Some(0),
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RestartConfig {
+    pub policy: RestartPolicy,
+    pub max_retries: u32,
+    pub window_seconds: u64,
+    pub backoff_seconds: u64,
+    pub max_backoff_seconds: u64,
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            policy: RestartPolicy::Never,
+            max_retries: 5,
+            window_seconds: 600,
+            backoff_seconds: 5,
+            max_backoff_seconds: 300,
+        }
+    }
+}
+
+impl RestartConfig {
+    /// The exponential backoff delay (in seconds) before the given restart attempt.
+    #[inline]
+    pub fn delay(&self, attempt: u32) -> u64 {
+        self.backoff_seconds
+            .saturating_mul(
+                1u64.checked_shl(attempt.saturating_sub(1))
+                    .unwrap_or(u64::MAX),
+            )
+            .min(self.max_backoff_seconds)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WatchdogConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    pub timeout_seconds: u64,
+    pub max_failures: u32,
+    /// How long to wait for the server to finish booting before pinging it anyway.
+    pub grace_seconds: u64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: 30,
+            timeout_seconds: 5,
+            max_failures: 3,
+            grace_seconds: 600,
+        }
+    }
+}
+
+/// Shell commands run around lifecycle events, see `hooks.rs`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HooksConfig {
+    pub pre_start: Option<String>,
+    pub post_start: Option<String>,
+    pub pre_stop: Option<String>,
+    pub post_stop: Option<String>,
+    pub on_crash: Option<String>,
+    pub pre_install: Option<String>,
+    pub post_update: Option<String>,
+}
+
+/// Resource limits of the java process, see `limits.rs`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LimitsConfig {
+    pub nice: Option<i32>,
+    /// The cpus the server may run on, e.g. `[0, 1, 2, 3]`.
+    pub cpu_affinity: Vec<usize>,
+    /// The `RLIMIT_NOFILE` (open files) of the server.
+    pub nofile: Option<u64>,
+    /// The cgroup v2 `memory.max` of the server (includes memory outside of the heap).
+    #[serde(rename = "memoryMaxMB")]
+    pub memory_max_mb: Option<u64>,
+    /// The cgroup v2 `cpu.max` of the server, `200` allows two full cpus.
+    pub cpu_max_percent: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CountdownConfig {
+    /// The console command broadcasting a message, e.g. `say {message}` or
+    /// `tellraw @a {"text":"{message}","color":"gold"}`.
+    pub broadcast: String,
+    pub stop_message: String,
+    pub restart_message: String,
+    pub abort_message: String,
+    /// The remaining times at which the message is broadcast.
+    pub warnings: Vec<String>,
+}
+
+impl Default for CountdownConfig {
+    fn default() -> Self {
+        Self {
+            broadcast: "say {message}".to_string(),
+            stop_message: "Server stopping in {time}".to_string(),
+            restart_message: "Server restarting in {time}".to_string(),
+            abort_message: "Server {action} has been cancelled".to_string(),
+            warnings: [
+                "30m", "15m", "10m", "5m", "1m", "30s", "10s", "5s", "4s", "3s", "2s", "1s",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+impl CountdownConfig {
+    #[inline]
+    pub fn format(&self, message: &str) -> String {
+        self.broadcast.replace("{message}", message)
+    }
+
+    /// The warnings to broadcast during a countdown of `duration`, starting with `duration` itself.
+    pub fn warnings(&self, duration: Duration) -> Vec<Duration> {
+        let mut warnings = vec![duration];
+        warnings.extend(
+            self.warnings
+                .iter()
+                .filter_map(|warning| humantime::parse_duration(warning).ok())
+                .filter(|warning| *warning < duration),
+        );
+
+        warnings
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum ScheduleAction {
+    Command {
+        command: String,
+    },
+    Restart {
+        #[serde(default)]
+        warnings: Vec<String>,
+    },
+    Backup {
+        #[serde(default)]
+        keep: Option<usize>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleEntry {
+    pub name: String,
+    pub cron: String,
+
+    #[serde(flatten)]
+    pub action: ScheduleAction,
+}
+
+impl ScheduleEntry {
+    /// Checks the parts of the entry that end up in file names, backups are stored as
+    /// `.mcvcli/backups/{name}-{timestamp}.tar.gz`.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() || self.name.contains(['/', '\\']) || self.name.contains("..") {
+            return Err(format!(
+                "scheduled task name `{}` must not be empty or contain `/`, `\\` or `..`",
+                self.name
+            ));
+        }
+
+        if let ScheduleAction::Backup { keep: Some(0) } = self.action {
+            return Err(format!(
+                "scheduled task `{}` must keep at least one backup",
+                self.name
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RconConfig {
+    pub host: String,
+    pub port: Option<u16>,
+    pub password: Option<String>,
+}
+
+impl Default for RconConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: None,
+            password: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationEvent {
+    Started,
+    Ready,
+    Stopped,
+    Crashed,
+    RestartLoop,
+    PlayerJoin,
+    PlayerLeave,
+    UpdateAvailable,
+    BackupFinished,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookFormat {
+    #[default]
+    Generic,
+    Discord,
+    Slack,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// The events sent to this webhook, all of them if empty.
+    #[serde(default)]
+    pub events: Vec<NotificationEvent>,
+    /// A JSON payload replacing `format`, `{event}`, `{server}`, `{profile}`, `{message}`
+    /// and `{time}` are substituted.
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+impl WebhookConfig {
+    #[inline]
+    pub fn wants(&self, event: NotificationEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Webhooks notified about server events, see `notify.rs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationsConfig {
+    pub webhooks: Vec<WebhookConfig>,
+    pub retries: u32,
+    /// The notifications sent to a single webhook per minute, more are dropped.
+    pub rate_limit_per_minute: u32,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            webhooks: Vec::new(),
+            retries: 3,
+            rate_limit_per_minute: 30,
+        }
+    }
+}
+
+impl NotificationsConfig {
+    #[inline]
+    pub fn wants(&self, event: NotificationEvent) -> bool {
+        self.webhooks.iter().any(|webhook| webhook.wants(event))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StopTransport {
+    #[default]
+    Console,
+    Rcon,
+}
+
+/// Finds the server root by walking up from `directory` to the nearest `.mcvcli.json`,
+/// the inactive profiles in `.mcvcli.profiles` are skipped.
+pub fn discover(directory: &Path) -> Option<PathBuf> {
+    directory
+        .ancestors()
+        .filter(|directory| {
+            directory.parent().and_then(|parent| parent.file_name())
+                != Some(".mcvcli.profiles".as_ref())
+        })
+        .find(|directory| directory.join(".mcvcli.json").is_file())
+        .map(Path::to_path_buf)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    #[serde(skip)]
+    path: String,
+    /// The schema version of the file if it was older, it is upgraded on the next save.
+    #[serde(skip)]
+    migrated_from: Option<u32>,
+
+    pub schema_version: u32,
+
+    pub jar_file: String,
+    pub stop_command: String,
+    pub stop_transport: StopTransport,
+    pub profile_name: String,
+
+    pub modpack_slug: Option<String>,
+    pub modpack_version: Option<String>,
+
+    #[serde(rename = "ramMB")]
+    pub ram_mb: u32,
+
+    pub java_version: u8,
+
+    pub extra_flags: Vec<String>,
+    pub extra_args: Vec<String>,
+
+    pub console_log: ConsoleLogConfig,
+    pub restart: RestartConfig,
+    pub watchdog: WatchdogConfig,
+    pub countdown: CountdownConfig,
+    pub hooks: HooksConfig,
+    pub env: IndexMap<String, String>,
+    /// A `.env` file (relative to the server directory) loaded before `env`.
+    pub env_file: Option<String>,
+    pub limits: LimitsConfig,
+    pub schedule: Vec<ScheduleEntry>,
+    pub rcon: RconConfig,
+    pub notifications: NotificationsConfig,
+
+    pub pid: Option<usize>,
+    pub identifier: Option<String>,
+}
+
+impl Config {
+    pub fn new(path: &str, create: bool) -> Result<Self, Error> {
+        if !Path::new(path).exists() {
+            if create {
+                let config = Config {
+                    path: path.to_string(),
+                    migrated_from: None,
+                    schema_version: SCHEMA_VERSION,
+                    jar_file: "server.jar".to_string(),
+                    stop_command: "stop".to_string(),
+                    stop_transport: StopTransport::Console,
+                    profile_name: "default".to_string(),
+                    modpack_slug: None,
+                    modpack_version: None,
+                    ram_mb: 2048,
+                    java_version: 21,
+                    extra_flags: Vec::new(),
+                    extra_args: Vec::new(),
+                    console_log: ConsoleLogConfig::default(),
+                    restart: RestartConfig::default(),
+                    watchdog: WatchdogConfig::default(),
+                    countdown: CountdownConfig::default(),
+                    hooks: HooksConfig::default(),
+                    env: IndexMap::new(),
+                    env_file: None,
+                    limits: LimitsConfig::default(),
+                    schedule: Vec::new(),
+                    rcon: RconConfig::default(),
+                    notifications: NotificationsConfig::default(),
+                    pid: None,
+                    identifier: Some(
+                        rand::rng()
+                            .sample_iter(&Alphanumeric)
+                            .take(7)
+                            .map(char::from)
+                            .collect(),
+                    ),
+                };
+
+                let file = File::create(path)?;
+                serde_json::to_writer_pretty(file, &config)
+                    .map_err(|err| Error::io(format!("unable to write {path}: {err}")))?;
+
+                return Ok(config);
+            } else {
+                return Err(Error::ConfigMissing);
+            }
+        }
+
+        let file = File::open(path)?;
+        let mut value: Value = serde_json::from_reader(file)
+            .map_err(|err| Error::InvalidConfig(format!("unable to parse `{path}`: {err}")))?;
+
+        let version = migrations::migrate(&mut value)
+            .map_err(|err| Error::InvalidConfig(format!("unable to migrate `{path}`: {err}")))?;
+
+        let mut config = Config::deserialize(&value)
+            .map_err(|err| Error::InvalidConfig(format!("unable to parse `{path}`: {err}")))?;
+        config.path = path.to_string();
+        config.migrated_from = (version < SCHEMA_VERSION).then_some(version);
+
+        if let Some(cpu) = config
+            .limits
+            .cpu_affinity
+            .iter()
+            .find(|cpu| **cpu >= limits::MAX_CPUS)
+        {
+            return Err(Error::InvalidConfig(format!(
+                "cpu {cpu} in `limits.cpuAffinity` of `{path}` is out of range, expected 0 to {}",
+                limits::MAX_CPUS - 1
+            )));
+        }
+
+        for entry in &config.schedule {
+            entry
+                .validate()
+                .map_err(|err| Error::InvalidConfig(format!("{err} in `{path}`")))?;
+        }
+
+        Ok(config)
+    }
+
+    /// Loads the config if it exists and is valid, for background tasks that cannot report errors.
+    pub fn new_optional(path: &str) -> Option<Self> {
+        Self::new(path, false).ok()
+    }
+
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The variables of the `env_file`, empty if there is none.
+    pub fn env_file(&self) -> Result<Vec<(String, String)>, String> {
+        let mut environment = Vec::new();
+
+        if let Some(env_file) = &self.env_file {
+            let path = Path::new(&self.path)
+                .parent()
+                .unwrap_or(Path::new("."))
+                .join(env_file);
+            let content = std::fs::read_to_string(&path)
+                .map_err(|err| format!("unable to read {}: {err}", path.display()))?;
+
+            for line in content.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+
+                let line = line.strip_prefix("export ").unwrap_or(line);
+                let Some((key, value)) = line.split_once('=') else {
+                    continue;
+                };
+
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .or_else(|| {
+                        value
+                            .strip_prefix('\'')
+                            .and_then(|value| value.strip_suffix('\''))
+                    })
+                    .unwrap_or(value);
+
+                environment.push((key.trim().to_string(), value.to_string()));
+            }
+        }
+
+        Ok(environment)
+    }
+
+    /// The environment variables of the server, the `env_file` followed by `env`. An
+    /// unreadable `env_file` is skipped, check `env_file` to report it.
+    pub fn environment(&self) -> Vec<(String, String)> {
+        let mut environment = self.env_file().unwrap_or_default();
+        environment.extend(
+            self.env
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+
+        environment
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        // loading never writes, an older file is only upgraded when it is saved anyway
+        if let Some(version) = self.migrated_from {
+            let backup = format!("{}.v{version}.bak", self.path);
+
+            if !Path::new(&backup).exists() {
+                std::fs::copy(&self.path, &backup)?;
+
+                eprintln!(
+                    "{} {} {} {}",
+                    "upgraded".bright_black(),
+                    self.path.cyan(),
+                    format!("to schema version {SCHEMA_VERSION}, the old file was kept as")
+                        .bright_black(),
+                    backup.cyan()
+                );
+            }
+        }
+
+        let file = File::create(&self.path)?;
+        serde_json::to_writer_pretty(file, &self)?;
+
+        Ok(())
+    }
+}
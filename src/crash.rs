@@ -0,0 +1,210 @@
+use regex::Regex;
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+use zip::ZipArchive;
+
+pub const DIRECTORY: &str = "crash-reports";
+
+static EXCEPTION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:Caused by: )?((?:[a-zA-Z_$][\w$]*\.)+[\w$]*(?:Exception|Error|Throwable)[\w$]*)(?:: (.*))?$")
+        .unwrap()
+});
+static FRAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s+at (?:[\w.@+-]*/)*([\w$.]+)\.([\w$<>]+)\((.*?)\)").unwrap());
+
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub class: String,
+    pub method: String,
+    pub source: String,
+}
+
+impl Frame {
+    /// The package of the frame's class, `None` for classes in the default package.
+    pub fn package(&self) -> Option<&str> {
+        self.class.rsplit_once('.').map(|(package, _)| package)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Exception {
+    pub class: String,
+    pub message: Option<String>,
+    pub frames: Vec<Frame>,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub time: Option<String>,
+    pub description: Option<String>,
+    /// The exception chain, the thrown exception first and the root cause last.
+    pub exceptions: Vec<Exception>,
+    /// The lines of the `Mod List` (forge) or `Fabric Mods` section.
+    pub mods: Vec<String>,
+}
+
+/// Parses a `crash-reports/*.txt` file. Only the stack trace at the top of the
+/// report is used, the detailed walkthrough below repeats parts of it.
+pub fn parse(content: &str) -> Report {
+    let mut report = Report::default();
+    let mut in_trace = true;
+    let mut in_mods = false;
+
+    for line in content.lines() {
+        if let Some(time) = line.strip_prefix("Time: ") {
+            report.time = Some(time.trim().to_string());
+            continue;
+        }
+        if let Some(description) = line.strip_prefix("Description: ") {
+            report.description = Some(description.trim().to_string());
+            continue;
+        }
+
+        if line.starts_with("A detailed walkthrough of the error") {
+            in_trace = false;
+        }
+
+        let trimmed = line.trim();
+
+        if in_mods {
+            // mod list entries are indented by two tabs, the section ends with the next detail
+            if line.starts_with("\t\t") && !trimmed.is_empty() {
+                report.mods.push(trimmed.to_string());
+                continue;
+            }
+
+            in_mods = false;
+        }
+
+        if trimmed == "Mod List:" || trimmed == "Fabric Mods:" {
+            in_mods = true;
+            continue;
+        }
+
+        if !in_trace {
+            continue;
+        }
+
+        if let Some(captures) = EXCEPTION.captures(trimmed) {
+            report.exceptions.push(Exception {
+                class: captures[1].to_string(),
+                message: captures.get(2).map(|message| message.as_str().to_string()),
+                frames: Vec::new(),
+            });
+        } else if let Some(captures) = FRAME.captures(line)
+            && let Some(exception) = report.exceptions.last_mut()
+        {
+            exception.frames.push(Frame {
+                class: captures[1].to_string(),
+                method: captures[2].to_string(),
+                source: captures[3].to_string(),
+            });
+        }
+    }
+
+    report
+}
+
+/// Returns the most recently written crash report.
+pub fn latest() -> Option<PathBuf> {
+    std::fs::read_dir(DIRECTORY)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "txt")
+        })
+        .max_by_key(|entry| {
+            entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .map(|entry| entry.path())
+}
+
+/// Maps every package that contains classes to the jars in `folder` that contain it.
+pub fn packages(folder: &Path) -> HashMap<String, Vec<PathBuf>> {
+    let mut packages: HashMap<String, Vec<PathBuf>> = HashMap::new();
+
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return packages;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().unwrap_or_default() != "jar" {
+            continue;
+        }
+
+        let Ok(archive) = File::open(&path)
+            .map_err(zip::result::ZipError::from)
+            .and_then(ZipArchive::new)
+        else {
+            continue;
+        };
+
+        let mut seen = HashSet::new();
+        for name in archive.file_names() {
+            let Some((package, _)) = name
+                .strip_suffix(".class")
+                .and_then(|name| name.rsplit_once('/'))
+            else {
+                continue;
+            };
+
+            // multi release jars keep copies of their classes in META-INF/versions/{n}/
+            if package.starts_with("META-INF") {
+                continue;
+            }
+
+            seen.insert(package.replace('/', "."));
+        }
+
+        for package in seen {
+            packages.entry(package).or_default().push(path.clone());
+        }
+    }
+
+    packages
+}
+
+#[derive(Debug)]
+pub struct Suspect {
+    pub jar: PathBuf,
+    pub frames: usize,
+    pub first: Frame,
+}
+
+/// Blames the jars owning the classes in the stack trace, in the order they show up
+/// starting from the root cause (the deepest `Caused by`).
+pub fn suspects(report: &Report, packages: &HashMap<String, Vec<PathBuf>>) -> Vec<Suspect> {
+    let mut suspects: Vec<Suspect> = Vec::new();
+
+    for exception in report.exceptions.iter().rev() {
+        for frame in exception.frames.iter() {
+            let Some(jars) = frame.package().and_then(|package| packages.get(package)) else {
+                continue;
+            };
+
+            for jar in jars {
+                match suspects.iter_mut().find(|suspect| &suspect.jar == jar) {
+                    Some(suspect) => suspect.frames += 1,
+                    None => suspects.push(Suspect {
+                        jar: jar.clone(),
+                        frames: 1,
+                        first: frame.clone(),
+                    }),
+                }
+            }
+        }
+    }
+
+    suspects
+}
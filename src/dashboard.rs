@@ -0,0 +1,581 @@
+use crate::config::Config;
+use crate::players::{self, Activity};
+use crate::supervisor::{
+    client::Client,
+    protocol::{self, Event, Request, Response, State},
+};
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event as TerminalEvent, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Sparkline},
+};
+use regex::Regex;
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate};
+use tokio::sync::mpsc;
+
+const MAX_LINES: usize = 5000;
+const MAX_SAMPLES: usize = 300;
+const MAX_PROBLEMS: usize = 50;
+
+static FORMATTING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m|§.").unwrap());
+static LIST: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"There are (\d+) of a max(?: of)? (\d+) players online:?(.*)").unwrap()
+});
+
+/// How the dashboard was opened, quitting a foreground dashboard stops the server.
+#[derive(Debug, Clone, Copy)]
+pub enum Mode {
+    Detached,
+    Foreground { timeout: u64 },
+}
+
+enum Message {
+    Response(Response),
+    Closed,
+    Terminal(TerminalEvent),
+}
+
+struct Dashboard {
+    title: String,
+    mode: Mode,
+    ram_mb: u32,
+
+    lines: VecDeque<String>,
+    scroll: usize,
+    input: String,
+    history: Vec<String>,
+    history_index: Option<usize>,
+
+    state: Option<State>,
+    stopping: bool,
+    cpu: VecDeque<u64>,
+    memory: VecDeque<u64>,
+    players: BTreeSet<String>,
+    max_players: Option<u32>,
+    problems: VecDeque<String>,
+}
+
+/// Removes ansi escapes and `§` formatting codes from a console line.
+fn strip_formatting(line: &str) -> String {
+    FORMATTING.replace_all(line, "").replace('\t', "    ")
+}
+
+fn line_style(line: &str) -> Style {
+    if line.contains("ERROR]") || line.contains("FATAL]") || line.contains("Exception") {
+        Style::default().fg(Color::Red)
+    } else if line.contains("WARN]") {
+        Style::default().fg(Color::Yellow)
+    } else if line.contains("joined the game") || line.contains("left the game") {
+        Style::default().fg(Color::Cyan)
+    } else if line.contains("Done (") {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    }
+}
+
+fn format_uptime(seconds: i64) -> String {
+    format!(
+        "{}h {}m {}s",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+impl Dashboard {
+    fn push_line(&mut self, line: &str) {
+        let line = strip_formatting(line);
+
+        if let Some(captures) = LIST.captures(&line) {
+            self.max_players = captures[2].parse().ok();
+            self.players = captures[3]
+                .split(',')
+                .map(|player| player.trim().to_string())
+                .filter(|player| !player.is_empty())
+                .collect();
+        } else {
+            match players::activity(&line) {
+                Some(Activity::Join(name)) => {
+                    self.players.insert(name);
+                }
+                Some(Activity::Leave(name)) => {
+                    self.players.remove(&name);
+                }
+                _ => {}
+            }
+        }
+
+        if line.contains("WARN]") || line.contains("ERROR]") || line.contains("FATAL]") {
+            self.problems.push_back(line.clone());
+            if self.problems.len() > MAX_PROBLEMS {
+                self.problems.pop_front();
+            }
+        }
+
+        self.lines.push_back(line);
+        if self.lines.len() > MAX_LINES {
+            self.lines.pop_front();
+        }
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.lines.len());
+        }
+    }
+
+    fn sample(&mut self, system: &mut sysinfo::System) {
+        let Some(pid) = self.state.as_ref().and_then(|state| state.pid) else {
+            return;
+        };
+
+        let pid = sysinfo::Pid::from_u32(pid);
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+
+        if let Some(process) = system.process(pid) {
+            self.cpu.push_back(process.cpu_usage().round() as u64);
+            self.memory.push_back(process.memory() / 1024 / 1024);
+
+            if self.cpu.len() > MAX_SAMPLES {
+                self.cpu.pop_front();
+                self.memory.pop_front();
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, input, help] = Layout::vertical([
+            Constraint::Min(5),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [console, side] =
+            Layout::horizontal([Constraint::Min(40), Constraint::Length(40)]).areas(main);
+
+        self.draw_console(frame, console);
+        self.draw_side(frame, side);
+
+        let prompt = Paragraph::new(Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Cyan)),
+            Span::raw(&self.input),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title(" command "));
+        frame.render_widget(prompt, input);
+        frame.set_cursor_position(Position::new(
+            (input.x + 3 + self.input.chars().count() as u16).min(input.right().saturating_sub(2)),
+            input.y + 1,
+        ));
+
+        let help_text = match self.mode {
+            Mode::Detached => {
+                " enter: run command · ↑/↓: history · pgup/pgdn: scroll · esc: detach"
+            }
+            Mode::Foreground { .. } => {
+                " enter: run command · ↑/↓: history · pgup/pgdn: scroll · ctrl-c: stop server"
+            }
+        };
+        frame.render_widget(
+            Paragraph::new(help_text).style(Style::default().fg(Color::DarkGray)),
+            help,
+        );
+    }
+
+    fn draw_console(&self, frame: &mut Frame, area: Rect) {
+        let width = area.width.saturating_sub(2).max(1) as usize;
+        let height = area.height.saturating_sub(2) as usize;
+
+        // wrap the newest lines until the pane is filled, skipping `scroll` lines
+        let mut rows: Vec<Line> = Vec::new();
+        for line in self.lines.iter().rev().skip(self.scroll) {
+            let style = line_style(line);
+            let chars = line.chars().collect::<Vec<_>>();
+            let chunks = chars
+                .chunks(width)
+                .map(|chunk| Line::styled(chunk.iter().collect::<String>(), style))
+                .collect::<Vec<_>>();
+
+            for chunk in chunks.into_iter().rev() {
+                rows.push(chunk);
+            }
+            if chars.is_empty() {
+                rows.push(Line::raw(""));
+            }
+
+            if rows.len() >= height {
+                break;
+            }
+        }
+        rows.truncate(height);
+        rows.reverse();
+
+        let title = if self.scroll > 0 {
+            format!(" {} (scrolled up {} lines) ", self.title, self.scroll)
+        } else {
+            format!(" {} ", self.title)
+        };
+
+        frame.render_widget(
+            Paragraph::new(rows).block(Block::default().borders(Borders::ALL).title(title)),
+            area,
+        );
+    }
+
+    fn draw_side(&self, frame: &mut Frame, area: Rect) {
+        let [server, cpu, memory, players, problems] = Layout::vertical([
+            Constraint::Length(6),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Min(4),
+            Constraint::Min(4),
+        ])
+        .areas(area);
+
+        let label = Style::default().fg(Color::DarkGray);
+        let value = Style::default().fg(Color::Cyan);
+
+        let (status, status_style) = match &self.state {
+            _ if self.stopping => ("stopping", Color::Yellow),
+            Some(state) if state.stopping => ("stopping", Color::Yellow),
+            Some(State { pid: None, .. }) => ("restarting", Color::Yellow),
+            Some(State { ready: false, .. }) => ("starting", Color::Yellow),
+            Some(_) => ("running", Color::Green),
+            None => ("connecting", Color::DarkGray),
+        };
+
+        let mut info = vec![Line::from(vec![
+            Span::styled("status:  ", label),
+            Span::styled(status, Style::default().fg(status_style)),
+        ])];
+        if let Some(state) = &self.state {
+            info.push(Line::from(vec![
+                Span::styled("pid:     ", label),
+                Span::styled(
+                    state
+                        .pid
+                        .map(|pid| pid.to_string())
+                        .unwrap_or("-".to_string()),
+                    value,
+                ),
+            ]));
+            info.push(Line::from(vec![
+                Span::styled("uptime:  ", label),
+                Span::styled(
+                    format_uptime(chrono::Utc::now().timestamp() - state.started),
+                    value,
+                ),
+            ]));
+            info.push(Line::from(vec![
+                Span::styled("startup: ", label),
+                Span::styled(
+                    state
+                        .startup_ms
+                        .map(|startup_ms| format!("{:.1}s", startup_ms as f64 / 1000.0))
+                        .unwrap_or("-".to_string()),
+                    value,
+                ),
+            ]));
+        }
+        frame.render_widget(
+            Paragraph::new(info).block(Block::default().borders(Borders::ALL).title(" server ")),
+            server,
+        );
+
+        let cpu_data = self.tail(&self.cpu, cpu);
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::default().borders(Borders::ALL).title(format!(
+                    " cpu {}% ",
+                    self.cpu.back().copied().unwrap_or_default()
+                )))
+                .data(&cpu_data)
+                .max(cpu_data.iter().copied().max().unwrap_or_default().max(100))
+                .style(Style::default().fg(Color::Green)),
+            cpu,
+        );
+
+        let memory_data = self.tail(&self.memory, memory);
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::default().borders(Borders::ALL).title(format!(
+                    " memory {} / {} MB ",
+                    self.memory.back().copied().unwrap_or_default(),
+                    self.ram_mb
+                )))
+                .data(&memory_data)
+                .max(
+                    memory_data
+                        .iter()
+                        .copied()
+                        .max()
+                        .unwrap_or_default()
+                        .max(self.ram_mb as u64),
+                )
+                .style(Style::default().fg(Color::Magenta)),
+            memory,
+        );
+
+        let title = match self.max_players {
+            Some(max) => format!(" players {}/{max} ", self.players.len()),
+            None => format!(" players {} ", self.players.len()),
+        };
+        frame.render_widget(
+            Paragraph::new(
+                self.players
+                    .iter()
+                    .map(|player| Line::styled(player.as_str(), value))
+                    .collect::<Vec<_>>(),
+            )
+            .block(Block::default().borders(Borders::ALL).title(title)),
+            players,
+        );
+
+        let height = problems.height.saturating_sub(2) as usize;
+        let width = problems.width.saturating_sub(2) as usize;
+        frame.render_widget(
+            Paragraph::new(
+                self.problems
+                    .iter()
+                    .skip(self.problems.len().saturating_sub(height))
+                    .map(|problem| {
+                        // the message is more useful than the timestamp and thread
+                        let message = problem
+                            .split_once("]: ")
+                            .map(|(_, message)| message)
+                            .unwrap_or(problem);
+
+                        Line::styled(
+                            message.chars().take(width).collect::<String>(),
+                            line_style(problem),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" warnings & errors "),
+            ),
+            problems,
+        );
+    }
+
+    /// The newest samples that fit into the sparkline.
+    fn tail(&self, samples: &VecDeque<u64>, area: Rect) -> Vec<u64> {
+        let width = area.width.saturating_sub(2) as usize;
+
+        samples
+            .iter()
+            .skip(samples.len().saturating_sub(width))
+            .copied()
+            .collect()
+    }
+}
+
+/// Runs the full-screen dashboard of the server until it exits or the user detaches.
+pub async fn run(config: &Config, identifier: &str, mode: Mode) -> std::io::Result<()> {
+    let client = Client::connect(identifier).await?;
+    let Client {
+        mut reader,
+        mut writer,
+    } = client;
+
+    protocol::write(&mut writer, &Request::Subscribe { tail: 500 }).await?;
+    protocol::write(&mut writer, &Request::State).await?;
+    protocol::write(
+        &mut writer,
+        &Request::Command {
+            command: "list".to_string(),
+        },
+    )
+    .await?;
+
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+
+    let socket = tokio::spawn({
+        let sender = sender.clone();
+
+        async move {
+            loop {
+                match protocol::read(&mut reader).await {
+                    Ok(Some(response)) => {
+                        if sender.send(Message::Response(response)).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) | Err(_) => {
+                        sender.send(Message::Closed).unwrap_or_default();
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    // crossterm only offers blocking reads without its event-stream feature
+    let done = Arc::new(AtomicBool::new(false));
+    let input = std::thread::spawn({
+        let done = Arc::clone(&done);
+
+        move || {
+            while !done.load(Ordering::SeqCst) {
+                if event::poll(Duration::from_millis(100)).unwrap_or(false)
+                    && let Ok(event) = event::read()
+                    && sender.send(Message::Terminal(event)).is_err()
+                {
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut dashboard = Dashboard {
+        title: format!("console ({})", config.profile_name),
+        mode,
+        ram_mb: config.ram_mb,
+        lines: VecDeque::new(),
+        scroll: 0,
+        input: String::new(),
+        history: Vec::new(),
+        history_index: None,
+        state: None,
+        stopping: false,
+        cpu: VecDeque::new(),
+        memory: VecDeque::new(),
+        players: BTreeSet::new(),
+        max_players: None,
+        problems: VecDeque::new(),
+    };
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut dashboard, &mut receiver, &mut writer).await;
+    ratatui::restore();
+
+    done.store(true, Ordering::SeqCst);
+    input.join().unwrap_or_default();
+    socket.abort();
+
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    dashboard: &mut Dashboard,
+    receiver: &mut mpsc::UnboundedReceiver<Message>,
+    writer: &mut tokio::net::unix::OwnedWriteHalf,
+) -> std::io::Result<()> {
+    let mut system = sysinfo::System::new();
+    let mut tick = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        terminal.draw(|frame| dashboard.draw(frame))?;
+
+        tokio::select! {
+            _ = tick.tick() => {
+                dashboard.sample(&mut system);
+                protocol::write(writer, &Request::State).await?;
+            }
+            message = receiver.recv() => match message {
+                Some(Message::Response(Response::Event { event })) => match event {
+                    Event::Line { line } => dashboard.push_line(&line),
+                    Event::Exited { .. } => return Ok(()),
+                    Event::ProcessExited { .. } => {
+                        dashboard.players.clear();
+                    }
+                    Event::Ready { .. } | Event::Aborted => {}
+                },
+                Some(Message::Response(Response::State { state })) => {
+                    dashboard.state = Some(state);
+                }
+                Some(Message::Response(Response::Error { message })) => {
+                    dashboard.push_line(&format!("[mcvcli/ERROR]: {message}"));
+                }
+                Some(Message::Response(Response::Ok | Response::Performance { .. })) => {}
+                Some(Message::Terminal(TerminalEvent::Key(key))) if key.kind == KeyEventKind::Press => {
+                    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+
+                    match key.code {
+                        KeyCode::Char('c') | KeyCode::Char('d') if control => match dashboard.mode {
+                            Mode::Detached => return Ok(()),
+                            Mode::Foreground { timeout } => {
+                                if !dashboard.stopping {
+                                    dashboard.stopping = true;
+                                    protocol::write(writer, &Request::Stop { timeout, countdown: 0 }).await?;
+                                }
+                            }
+                        },
+                        KeyCode::Esc => {
+                            if let Mode::Detached = dashboard.mode {
+                                return Ok(());
+                            }
+                        }
+                        KeyCode::Enter => {
+                            let command = std::mem::take(&mut dashboard.input);
+                            dashboard.history_index = None;
+                            dashboard.scroll = 0;
+
+                            if !command.trim().is_empty() {
+                                if dashboard.history.last() != Some(&command) {
+                                    dashboard.history.push(command.clone());
+                                }
+                                protocol::write(writer, &Request::Command { command }).await?;
+                            }
+                        }
+                        KeyCode::Backspace => {
+                            dashboard.input.pop();
+                        }
+                        KeyCode::Up if !dashboard.history.is_empty() => {
+                            let index = dashboard
+                                .history_index
+                                .map(|index| index.saturating_sub(1))
+                                .unwrap_or(dashboard.history.len() - 1);
+
+                            dashboard.history_index = Some(index);
+                            dashboard.input = dashboard.history[index].clone();
+                        }
+                        KeyCode::Down => {
+                            match dashboard.history_index {
+                                Some(index) if index + 1 < dashboard.history.len() => {
+                                    dashboard.history_index = Some(index + 1);
+                                    dashboard.input = dashboard.history[index + 1].clone();
+                                }
+                                Some(_) => {
+                                    dashboard.history_index = None;
+                                    dashboard.input.clear();
+                                }
+                                None => {}
+                            }
+                        }
+                        KeyCode::PageUp => {
+                            let page = terminal.size()?.height.saturating_sub(8).max(1) as usize;
+                            dashboard.scroll = (dashboard.scroll + page).min(dashboard.lines.len().saturating_sub(1));
+                        }
+                        KeyCode::PageDown => {
+                            let page = terminal.size()?.height.saturating_sub(8).max(1) as usize;
+                            dashboard.scroll = dashboard.scroll.saturating_sub(page);
+                        }
+                        KeyCode::End => dashboard.scroll = 0,
+                        KeyCode::Char(c) if !control => dashboard.input.push(c),
+                        _ => {}
+                    }
+                }
+                Some(Message::Terminal(_)) => {}
+                Some(Message::Closed) | None => return Ok(()),
+            },
+        }
+    }
+}
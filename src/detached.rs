@@ -1,4 +1,5 @@
-use ipipe::Pipe;
+use crate::config::Config;
+use crate::supervisor;
 
 pub fn status(pid: Option<usize>) -> bool {
     if pid.is_none() {
@@ -18,10 +19,11 @@ pub fn status(pid: Option<usize>) -> bool {
         .is_some_and(|s| s.contains("java"))
 }
 
-pub fn get_pipes(identifier: &str) -> [Pipe; 3] {
-    let stdin = Pipe::with_name(&format!("{identifier}_stdin")).unwrap();
-    let stdout = Pipe::with_name(&format!("{identifier}_stdout")).unwrap();
-    let stderr = Pipe::with_name(&format!("{identifier}_stderr")).unwrap();
-
-    [stdin, stdout, stderr]
+/// Whether the server is running, either java is alive or its supervisor is (e.g. while
+/// it waits to restart a crashed server).
+pub fn running(config: &Config) -> bool {
+    status(config.pid)
+        || config.identifier.as_deref().is_some_and(|identifier| {
+            std::os::unix::net::UnixStream::connect(supervisor::socket_path(identifier)).is_ok()
+        })
 }
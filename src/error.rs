@@ -0,0 +1,156 @@
+use colored::Colorize;
+use std::fmt::{self, Display};
+
+/// The errors of the commands, each category has a stable exit code (see `Error::code`).
+/// Text in backticks is highlighted when printed.
+#[derive(Debug)]
+pub enum Error {
+    /// Anything that does not fit another category.
+    Other(String),
+    /// `.mcvcli.json` does not exist.
+    ConfigMissing,
+    /// `.mcvcli.json` or another file managed by mcvcli could not be parsed or is invalid.
+    InvalidConfig(String),
+    /// A request to an api failed or timed out.
+    Network(String),
+    /// A profile, mod, version, player, ... does not exist.
+    NotFound(String),
+    /// The server is running, but the command needs it stopped.
+    Running(String),
+    /// The server is not running, but the command needs it running.
+    NotRunning(String),
+    /// The user declined a prompt or cancelled it with Ctrl-C.
+    Aborted,
+    /// Reading or writing a file or talking to a process failed.
+    Io(String),
+    /// Waiting for the server timed out.
+    TimedOut(String),
+    /// The server exited with a non-zero exit code in the foreground.
+    Exited(i32),
+}
+
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+impl Error {
+    #[inline]
+    pub fn other(message: impl Into<String>) -> Self {
+        Self::Other(message.into())
+    }
+
+    #[inline]
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    #[inline]
+    pub fn io(message: impl Display) -> Self {
+        Self::Io(message.to_string())
+    }
+
+    /// The server must be stopped first.
+    #[inline]
+    pub fn running() -> Self {
+        Self::Running("server is currently running, stop it first with `mcvcli stop`".to_string())
+    }
+
+    #[inline]
+    pub fn not_running() -> Self {
+        Self::NotRunning("server is not running, use `mcvcli start --detached`".to_string())
+    }
+
+    /// The exit code of the process, documented in the README.
+    pub fn code(&self) -> i32 {
+        match self {
+            Self::Other(_) => 1,
+            Self::ConfigMissing => 3,
+            Self::InvalidConfig(_) => 4,
+            Self::Network(_) => 5,
+            Self::NotFound(_) => 6,
+            Self::Running(_) => 7,
+            Self::NotRunning(_) => 8,
+            Self::Io(_) => 9,
+            Self::TimedOut(_) => 10,
+            Self::Aborted => 130,
+            Self::Exited(code) => *code,
+        }
+    }
+
+    /// Prints the error in red with the text in backticks highlighted.
+    pub fn print(&self) {
+        let message = match self {
+            Self::Exited(_) => return,
+            Self::Network(message) => format!("network error: {message}"),
+            Self::Io(message) => format!("i/o error: {message}"),
+            _ => self.to_string(),
+        };
+
+        let line = message
+            .split('`')
+            .enumerate()
+            .map(|(i, part)| {
+                if i % 2 == 1 {
+                    part.cyan().to_string()
+                } else {
+                    part.red().to_string()
+                }
+            })
+            .collect::<String>();
+
+        eprintln!("{line}");
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Other(message)
+            | Self::InvalidConfig(message)
+            | Self::Network(message)
+            | Self::NotFound(message)
+            | Self::Running(message)
+            | Self::NotRunning(message)
+            | Self::Io(message)
+            | Self::TimedOut(message) => write!(f, "{message}"),
+            Self::ConfigMissing => write!(
+                f,
+                "config file does not exist, run `mcvcli init .` to initialize a new server"
+            ),
+            Self::Aborted => write!(f, "aborted"),
+            Self::Exited(code) => write!(f, "server exited with code {code}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::Interrupted => Self::Aborted,
+            _ => Self::Io(err.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Network(err.to_string())
+    }
+}
+
+impl From<dialoguer::Error> for Error {
+    fn from(err: dialoguer::Error) -> Self {
+        match err {
+            dialoguer::Error::IO(err) => err.into(),
+        }
+    }
+}
+
+impl From<zip::result::ZipError> for Error {
+    fn from(err: zip::result::ZipError) -> Self {
+        match err {
+            zip::result::ZipError::Io(err) => err.into(),
+            err => Self::Io(format!("invalid archive: {err}")),
+        }
+    }
+}
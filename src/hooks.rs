@@ -0,0 +1,171 @@
+use crate::config::Config;
+use crate::jar;
+
+use colored::Colorize;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::process::Command;
+
+/// How long a hook may run before it is killed and counts as failed.
+pub const TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PreStart,
+    PostStart,
+    PreStop,
+    PostStop,
+    OnCrash,
+    PreInstall,
+    PostUpdate,
+}
+
+impl Hook {
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::PreStart => "pre_start",
+            Hook::PostStart => "post_start",
+            Hook::PreStop => "pre_stop",
+            Hook::PostStop => "post_stop",
+            Hook::OnCrash => "on_crash",
+            Hook::PreInstall => "pre_install",
+            Hook::PostUpdate => "post_update",
+        }
+    }
+
+    #[inline]
+    pub fn is_configured(self, config: &Config) -> bool {
+        self.script(config).is_some()
+    }
+
+    fn script(self, config: &Config) -> Option<&str> {
+        let hooks = &config.hooks;
+
+        match self {
+            Hook::PreStart => hooks.pre_start.as_deref(),
+            Hook::PostStart => hooks.post_start.as_deref(),
+            Hook::PreStop => hooks.pre_stop.as_deref(),
+            Hook::PostStop => hooks.post_stop.as_deref(),
+            Hook::OnCrash => hooks.on_crash.as_deref(),
+            Hook::PreInstall => hooks.pre_install.as_deref(),
+            Hook::PostUpdate => hooks.post_update.as_deref(),
+        }
+        .filter(|script| !script.trim().is_empty())
+    }
+}
+
+/// The server directory the config belongs to.
+fn directory(config: &Config) -> PathBuf {
+    match Path::new(config.path()).parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// The version and build id of the server jar for `MCVCLI_VERSION` and `MCVCLI_BUILD_ID`,
+/// empty if they cannot be detected within 10 seconds.
+pub async fn version(config: &Config) -> (String, String) {
+    let directory = directory(config);
+    let detected = tokio::time::timeout(
+        Duration::from_secs(10),
+        jar::detect(&directory.to_string_lossy(), config),
+    )
+    .await;
+
+    match detected {
+        Ok(Some(([build, _], _, _))) => (
+            build
+                .version_id
+                .or(build.project_version_id)
+                .unwrap_or_default(),
+            build.id.to_string(),
+        ),
+        _ => (String::new(), String::new()),
+    }
+}
+
+/// Builds the shell command of a hook, `None` if the hook is not configured.
+///
+/// The hook runs in the server directory with `MCVCLI_HOOK`, `MCVCLI_DIR`, `MCVCLI_PROFILE`,
+/// `MCVCLI_JAR`, `MCVCLI_VERSION` and `MCVCLI_BUILD_ID` (empty if unknown) set, `vars`
+/// adds to or overrides those.
+pub async fn command(hook: Hook, config: &Config, vars: &[(&str, String)]) -> Option<Command> {
+    let script = hook.script(config)?;
+    let directory = directory(config);
+
+    let (version, build_id) = if vars.iter().any(|(key, _)| *key == "MCVCLI_VERSION") {
+        (String::new(), String::new())
+    } else {
+        version(config).await
+    };
+
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(script);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    };
+
+    command
+        .current_dir(&directory)
+        .kill_on_drop(true)
+        .env("MCVCLI_HOOK", hook.name())
+        .env(
+            "MCVCLI_DIR",
+            std::path::absolute(&directory).unwrap_or(directory),
+        )
+        .env("MCVCLI_PROFILE", &config.profile_name)
+        .env("MCVCLI_JAR", &config.jar_file)
+        .env("MCVCLI_VERSION", version)
+        .env("MCVCLI_BUILD_ID", build_id);
+
+    for (key, value) in vars {
+        command.env(key, value);
+    }
+
+    Some(command)
+}
+
+/// Runs a hook with its output going to the terminal, fails if the hook does not succeed.
+pub async fn run(hook: Hook, config: &Config, vars: &[(&str, String)]) -> Result<(), String> {
+    let Some(mut command) = command(hook, config, vars).await else {
+        return Ok(());
+    };
+
+    println!(
+        "{} {} {}",
+        "running".bright_black(),
+        hook.name().cyan(),
+        "hook ...".bright_black()
+    );
+
+    let result = match tokio::time::timeout(TIMEOUT, command.status()).await {
+        Ok(Ok(status)) if status.success() => Ok(()),
+        Ok(Ok(status)) => Err(format!("{} hook has failed ({status})", hook.name())),
+        Ok(Err(err)) => Err(format!("unable to run {} hook: {err}", hook.name())),
+        Err(_) => Err(format!(
+            "{} hook has timed out after {}s",
+            hook.name(),
+            TIMEOUT.as_secs()
+        )),
+    };
+
+    println!(
+        "{} {} {} {}",
+        "running".bright_black(),
+        hook.name().cyan(),
+        "hook ...".bright_black(),
+        if result.is_ok() {
+            "DONE".green().bold()
+        } else {
+            "FAILED".red().bold()
+        }
+    );
+
+    result
+}
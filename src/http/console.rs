@@ -0,0 +1,130 @@
+use super::{CONFIG, error, missing_config};
+use crate::config::Config;
+use crate::detached;
+use crate::supervisor::{
+    client::Client,
+    protocol::{self, Event, Request, Response},
+};
+
+use axum::{
+    extract::{
+        Query, WebSocketUpgrade,
+        ws::{Message, WebSocket},
+    },
+    http::StatusCode,
+    response::{self, IntoResponse},
+};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+
+fn default_tail() -> usize {
+    100
+}
+
+#[derive(Deserialize)]
+pub struct ConsoleQuery {
+    #[serde(default = "default_tail")]
+    tail: usize,
+}
+
+/// Streams the console as json events and runs every text message as a command.
+pub async fn console(
+    upgrade: WebSocketUpgrade,
+    Query(query): Query<ConsoleQuery>,
+) -> response::Response {
+    let config = match Config::new_optional(CONFIG) {
+        Some(config) => config,
+        None => return missing_config(),
+    };
+
+    if !detached::running(&config) {
+        return error(StatusCode::CONFLICT, "server is not running");
+    }
+
+    let Some(identifier) = &config.identifier else {
+        return error(StatusCode::CONFLICT, "server is not running");
+    };
+
+    let client = match Client::connect(identifier).await {
+        Ok(client) => client,
+        Err(err) => return error(StatusCode::BAD_GATEWAY, err.to_string()),
+    };
+
+    upgrade
+        .on_upgrade(move |socket| bridge(socket, client, query.tail))
+        .into_response()
+}
+
+async fn bridge(socket: WebSocket, client: Client, tail: usize) {
+    let Client {
+        mut reader,
+        mut writer,
+    } = client;
+    let (mut sender, mut receiver) = socket.split();
+
+    if protocol::write(&mut writer, &Request::Subscribe { tail })
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    let mut input = tokio::spawn(async move {
+        while let Some(Ok(message)) = receiver.next().await {
+            match message {
+                Message::Text(command) => {
+                    if protocol::write(
+                        &mut writer,
+                        &Request::Command {
+                            command: command.to_string(),
+                        },
+                    )
+                    .await
+                    .is_err()
+                    {
+                        break;
+                    }
+                }
+                Message::Close(_) => break,
+                _ => continue,
+            }
+        }
+    });
+
+    let mut output = tokio::spawn(async move {
+        loop {
+            let response = match protocol::read(&mut reader).await {
+                Ok(Some(response @ (Response::Event { .. } | Response::Error { .. }))) => response,
+                Ok(Some(_)) => continue,
+                Ok(None) | Err(_) => break,
+            };
+
+            let exited = matches!(
+                response,
+                Response::Event {
+                    event: Event::Exited { .. }
+                }
+            );
+            let payload = match &response {
+                Response::Event { event } => serde_json::to_string(event),
+                _ => serde_json::to_string(&response),
+            };
+
+            if sender
+                .send(Message::Text(payload.unwrap().into()))
+                .await
+                .is_err()
+                || exited
+            {
+                break;
+            }
+        }
+
+        sender.send(Message::Close(None)).await.unwrap_or_default();
+    });
+
+    tokio::select! {
+        _ = &mut input => output.abort(),
+        _ = &mut output => input.abort(),
+    }
+}
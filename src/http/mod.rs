@@ -0,0 +1,497 @@
+use crate::config::{self, Config};
+use crate::tokens::Tokens;
+use crate::{api, detached, jar, java, profiles};
+
+use axum::{
+    Json, Router,
+    extract::{Path, Request},
+    http::{StatusCode, header::AUTHORIZATION},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::process::Stdio;
+
+mod console;
+
+const CONFIG: &str = ".mcvcli.json";
+
+/// The keys of the config that can be set, the same ones `mcvcli config` exposes. Hooks,
+/// the environment, the jar file and the java flags would let a token run arbitrary commands.
+const WRITABLE: &[&str] = &[
+    "ramMB",
+    "stopCommand",
+    "stopTransport",
+    "restart.policy",
+    "watchdog.enabled",
+];
+
+/// The secrets of the config, replaced by `REDACTED` in responses.
+const REDACTED: &str = "<redacted>";
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/api/status", get(status))
+        .route("/api/start", post(start))
+        .route("/api/stop", post(stop))
+        .route("/api/version", get(version))
+        .route("/api/mods", get(mods))
+        .route("/api/profiles", get(list_profiles))
+        .route("/api/profiles/{name}/use", post(use_profile))
+        .route("/api/java", get(list_java))
+        .route("/api/config", get(get_config).patch(set_config))
+        .route("/api/console", get(console::console))
+        .layer(middleware::from_fn(authenticate))
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": message.into() }))).into_response()
+}
+
+fn missing_config() -> Response {
+    error(
+        StatusCode::NOT_FOUND,
+        "config file does not exist, run mcvcli init",
+    )
+}
+
+/// Accepts the token as `Authorization: Bearer <token>` or as `?token=<token>`,
+/// browsers cannot set headers on websocket connections.
+async fn authenticate(request: Request, next: Next) -> Response {
+    let header = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let query = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+    });
+
+    match header.or(query) {
+        Some(token) if Tokens::load().verify(token.trim()).is_some() => next.run(request).await,
+        _ => error(StatusCode::UNAUTHORIZED, "missing or invalid token"),
+    }
+}
+
+#[derive(Serialize)]
+struct Output {
+    code: i32,
+    output: Vec<String>,
+}
+
+/// Runs a mcvcli command in the server directory, so that hooks, preflight checks
+/// and the supervisor handoff behave exactly like on the command line.
+async fn command(args: &[String]) -> std::io::Result<Output> {
+    let output = tokio::process::Command::new(std::env::current_exe()?)
+        .args(args)
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+
+    Ok(Output {
+        code: output.status.code().unwrap_or(1),
+        output: String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .chain(String::from_utf8_lossy(&output.stderr).lines())
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+async fn run(args: &[String]) -> Response {
+    match command(args).await {
+        Ok(output) if output.code == 0 => Json(output).into_response(),
+        Ok(output) => (StatusCode::UNPROCESSABLE_ENTITY, Json(output)).into_response(),
+        Err(err) => error(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn status() -> Response {
+    if Config::new_optional(CONFIG).is_none() {
+        return missing_config();
+    }
+
+    match command(&["status".to_string(), "--json".to_string()]).await {
+        Ok(output) => match serde_json::from_str::<Value>(&output.output.join("\n")) {
+            Ok(status) => Json(status).into_response(),
+            Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, Json(output)).into_response(),
+        },
+        Err(err) => error(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct StartRequest {
+    eula: bool,
+    force: bool,
+    wait_ready: bool,
+}
+
+async fn start(request: Option<Json<StartRequest>>) -> Response {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let Some(config) = Config::new_optional(CONFIG) else {
+        return missing_config();
+    };
+
+    if detached::running(&config) {
+        return error(StatusCode::CONFLICT, "server is already running");
+    }
+
+    let eula_accepted = std::fs::read_to_string("eula.txt")
+        .unwrap_or_default()
+        .contains("eula=true");
+    if !eula_accepted && !request.eula {
+        return error(
+            StatusCode::CONFLICT,
+            "the minecraft eula (https://minecraft.net/eula) has not been accepted, pass \"eula\": true",
+        );
+    }
+
+    let mut args = vec!["start".to_string(), "--detached".to_string()];
+    if request.eula {
+        args.push("--eula".to_string());
+    }
+    if request.force {
+        args.push("--force".to_string());
+    }
+    if request.wait_ready {
+        args.push("--wait-ready".to_string());
+    }
+
+    run(&args).await
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct StopRequest {
+    timeout: Option<u64>,
+    countdown: Option<String>,
+}
+
+async fn stop(request: Option<Json<StopRequest>>) -> Response {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let Some(config) = Config::new_optional(CONFIG) else {
+        return missing_config();
+    };
+
+    if !detached::running(&config) {
+        return error(StatusCode::CONFLICT, "server is not running");
+    }
+
+    let mut args = vec!["stop".to_string()];
+    if let Some(timeout) = request.timeout {
+        args.extend(["--timeout".to_string(), timeout.to_string()]);
+    }
+    if let Some(countdown) = request.countdown {
+        args.extend(["--countdown".to_string(), countdown]);
+    }
+
+    run(&args).await
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Modpack {
+    title: String,
+    description: String,
+    id: Option<String>,
+    version: Option<String>,
+    latest: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Version {
+    jar_file: String,
+    java_version: u8,
+    r#type: Option<String>,
+    version: Option<String>,
+    version_latest: bool,
+    build: Option<String>,
+    build_latest: bool,
+    modpack: Option<Modpack>,
+}
+
+async fn version() -> Response {
+    let Some(config) = Config::new_optional(CONFIG) else {
+        return missing_config();
+    };
+
+    let mut version = Version {
+        jar_file: config.jar_file.clone(),
+        java_version: config.java_version,
+        r#type: None,
+        version: None,
+        version_latest: false,
+        build: None,
+        build_latest: false,
+        modpack: None,
+    };
+
+    if let Some(([build, latest], versions, modpack)) = jar::detect(".", &config).await {
+        version.version_latest = jar::is_latest_version(&build, &versions);
+        version.build_latest = build.id == latest.id;
+        version.modpack = modpack.map(|modpack| Modpack {
+            latest: modpack.versions.last() == config.modpack_version.as_ref(),
+            title: modpack.title,
+            description: modpack.description,
+            id: modpack.id,
+            version: config.modpack_version.clone(),
+        });
+        version.r#type = Some(build.r#type);
+        version.version = build.version_id.or(build.project_version_id);
+        version.build = Some(build.name);
+    }
+
+    Json(version).into_response()
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Mod {
+    path: String,
+    title: String,
+    description: String,
+    downloads: u32,
+    version: Option<String>,
+    latest: Option<bool>,
+}
+
+async fn mods() -> Response {
+    let Some(config) = Config::new_optional(CONFIG) else {
+        return missing_config();
+    };
+
+    if !std::path::Path::new("mods").exists() {
+        return error(StatusCode::NOT_FOUND, "no mods folder found");
+    }
+
+    let Some(([build, _], _, _)) = jar::detect(".", &config).await else {
+        return error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "installed version could not be detected",
+        );
+    };
+
+    let version = build
+        .version_id
+        .or(build.project_version_id)
+        .unwrap_or("unknown".to_string());
+    let list =
+        match api::modrinth::lookup("mods", Some(&build.r#type.to_lowercase()), Some(&version))
+            .await
+        {
+            Ok(list) => list,
+            Err(err) => return error(StatusCode::BAD_GATEWAY, err.to_string()),
+        };
+
+    Json(
+        list.into_iter()
+            .map(|(path, project)| Mod {
+                path: path.display().to_string(),
+                latest: match (
+                    &project.installed_latest_version,
+                    &project.installed_version,
+                ) {
+                    (Some(latest), Some(installed)) => Some(latest.id == installed.id),
+                    _ => None,
+                },
+                version: project
+                    .installed_version
+                    .and_then(|version| version.name.or(version.version_number)),
+                title: project.title,
+                description: project.description,
+                downloads: project.downloads,
+            })
+            .collect::<Vec<_>>(),
+    )
+    .into_response()
+}
+
+#[derive(Serialize)]
+struct Profile {
+    name: String,
+    current: bool,
+}
+
+async fn list_profiles() -> Response {
+    let Some(config) = Config::new_optional(CONFIG) else {
+        return missing_config();
+    };
+
+    let mut list = profiles::list()
+        .into_iter()
+        .map(|name| Profile {
+            name,
+            current: false,
+        })
+        .collect::<Vec<_>>();
+    list.push(Profile {
+        name: config.profile_name,
+        current: true,
+    });
+
+    Json(list).into_response()
+}
+
+async fn use_profile(Path(name): Path<String>) -> Response {
+    let Some(config) = Config::new_optional(CONFIG) else {
+        return missing_config();
+    };
+
+    if detached::running(&config) {
+        return error(StatusCode::CONFLICT, "server is currently running");
+    }
+    if !profiles::list().contains(&name) {
+        return error(
+            StatusCode::NOT_FOUND,
+            format!("profile {name} does not exist"),
+        );
+    }
+
+    run(&["profile".to_string(), "use".to_string(), name]).await
+}
+
+#[derive(Serialize)]
+struct Java {
+    version: u8,
+    path: String,
+    current: bool,
+    local: bool,
+}
+
+async fn list_java() -> Response {
+    let current = Config::new_optional(CONFIG).map(|config| config.java_version);
+
+    let mut list = java::installed()
+        .into_iter()
+        .map(|(version, path)| Java {
+            version,
+            path,
+            current: Some(version) == current,
+            local: false,
+        })
+        .collect::<Vec<_>>();
+    if let Some((version, path, _)) = java::find_local() {
+        list.push(Java {
+            version,
+            path,
+            current: false,
+            local: true,
+        });
+    }
+
+    Json(list).into_response()
+}
+
+/// The config without the rcon password and the webhook urls (which contain their tokens).
+fn redacted(config: &Config) -> Value {
+    let mut value = serde_json::to_value(config).unwrap();
+
+    if let Some(password) = value.pointer_mut("/rcon/password")
+        && !password.is_null()
+    {
+        *password = REDACTED.into();
+    }
+    if let Some(Value::Array(webhooks)) = value.pointer_mut("/notifications/webhooks") {
+        for webhook in webhooks {
+            webhook["url"] = REDACTED.into();
+        }
+    }
+
+    value
+}
+
+async fn get_config() -> Response {
+    match Config::new_optional(CONFIG) {
+        Some(config) => Json(redacted(&config)).into_response(),
+        None => missing_config(),
+    }
+}
+
+/// Collects the dotted paths of the values set by `patch`.
+fn paths(patch: &Value, prefix: &str, paths: &mut Vec<String>) {
+    match patch {
+        Value::Object(fields) if !fields.is_empty() => {
+            for (key, value) in fields {
+                if prefix.is_empty() {
+                    self::paths(value, key, paths);
+                } else {
+                    self::paths(value, &format!("{prefix}.{key}"), paths);
+                }
+            }
+        }
+        _ => paths.push(prefix.to_string()),
+    }
+}
+
+/// Sets one of the `WRITABLE` keys on the config.
+fn apply(config: &mut Config, key: &str, value: Value) -> Result<(), serde_json::Error> {
+    match key {
+        "ramMB" => config.ram_mb = serde_json::from_value(value)?,
+        "stopCommand" => config.stop_command = serde_json::from_value(value)?,
+        "stopTransport" => config.stop_transport = serde_json::from_value(value)?,
+        "restart.policy" => config.restart.policy = serde_json::from_value(value)?,
+        "watchdog.enabled" => config.watchdog.enabled = serde_json::from_value(value)?,
+        _ => unreachable!("{key} is not writable"),
+    }
+
+    Ok(())
+}
+
+async fn set_config(Json(patch): Json<Value>) -> Response {
+    let Some(mut config) = Config::new_optional(CONFIG) else {
+        return missing_config();
+    };
+
+    if !patch.is_object() {
+        return error(StatusCode::BAD_REQUEST, "expected a json object");
+    }
+
+    let mut keys = Vec::new();
+    paths(&patch, "", &mut keys);
+    if let Some(key) = keys.iter().find(|key| !WRITABLE.contains(&key.as_str())) {
+        return error(
+            StatusCode::FORBIDDEN,
+            format!(
+                "{key} cannot be set over the api, settable keys are {}",
+                WRITABLE.join(", ")
+            ),
+        );
+    }
+
+    for key in keys {
+        let value = patch
+            .pointer(&format!("/{}", key.replace('.', "/")))
+            .cloned()
+            .unwrap_or_default();
+
+        if let Err(err) = apply(&mut config, &key, value) {
+            return error(StatusCode::BAD_REQUEST, format!("{key}: {err}"));
+        }
+    }
+
+    if !(config::MIN_RAM_MB..=config::MAX_RAM_MB).contains(&config.ram_mb) {
+        return error(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "ramMB must be between {} and {}",
+                config::MIN_RAM_MB,
+                config::MAX_RAM_MB
+            ),
+        );
+    }
+
+    // through `save` so an older file is backed up before it is upgraded
+    if let Err(err) = config.save() {
+        return error(StatusCode::INTERNAL_SERVER_ERROR, err.to_string());
+    }
+
+    Json(redacted(&config)).into_response()
+}
@@ -4,6 +4,7 @@ use crate::api::{
     modrinth::Project,
 };
 use crate::config::Config;
+use crate::error::Error;
 
 use colored::Colorize;
 use human_bytes::human_bytes;
@@ -13,7 +14,7 @@ use std::io::Write;
 use std::path::Path;
 use zip::ZipArchive;
 
-pub async fn install(build: &Build, directory: &str, spaces: usize) -> Result<(), reqwest::Error> {
+pub async fn install(build: &Build, directory: &str, spaces: usize) -> Result<(), Error> {
     if Path::new(directory).join("libraries").exists() {
         std::fs::remove_dir_all(Path::new(directory).join("libraries")).unwrap_or_default();
     }
@@ -38,12 +39,11 @@ pub async fn install(build: &Build, directory: &str, spaces: usize) -> Result<()
                     {
                         std::fs::create_dir_all(
                             Path::new(directory).join(&step.file).parent().unwrap(),
-                        )
-                        .unwrap();
+                        )?;
                     }
 
                     let mut res = api::CLIENT.get(&step.url).send().await?;
-                    let mut file = File::create(Path::new(directory).join(&step.file)).unwrap();
+                    let mut file = File::create(Path::new(directory).join(&step.file))?;
 
                     let mut progress = Progress::new(step.size as usize);
                     progress.spinner(move |progress, spinner| {
@@ -64,13 +64,13 @@ pub async fn install(build: &Build, directory: &str, spaces: usize) -> Result<()
                         )
                     });
 
-                    while let Some(chunk) = res.chunk().await.unwrap() {
-                        file.write_all(&chunk).unwrap();
+                    while let Some(chunk) = res.chunk().await? {
+                        file.write_all(&chunk)?;
 
                         progress.incr(chunk.len());
                     }
 
-                    file.sync_all().unwrap();
+                    file.sync_all()?;
                     progress.finish();
                     println!();
 
@@ -93,15 +93,12 @@ pub async fn install(build: &Build, directory: &str, spaces: usize) -> Result<()
                     );
 
                     if !Path::new(&step.location).exists() {
-                        std::fs::create_dir_all(Path::new(directory).join(&step.location)).unwrap();
+                        std::fs::create_dir_all(Path::new(directory).join(&step.location))?;
                     }
 
                     let mut archive =
-                        ZipArchive::new(File::open(Path::new(directory).join(&step.file)).unwrap())
-                            .unwrap();
-                    archive
-                        .extract(Path::new(directory).join(&step.location))
-                        .unwrap();
+                        ZipArchive::new(File::open(Path::new(directory).join(&step.file))?)?;
+                    archive.extract(Path::new(directory).join(&step.location))?;
 
                     println!(
                         "{}{} {} {} {}",
@@ -162,7 +159,7 @@ pub async fn detect(
             let path = entry.path();
 
             if path.is_dir() {
-                for entry in std::fs::read_dir(&path).unwrap().flatten() {
+                for entry in std::fs::read_dir(&path).into_iter().flatten().flatten() {
                     let path = entry.path();
 
                     if path.is_file() {
@@ -183,7 +180,7 @@ pub async fn detect(
             let path = entry.path();
 
             if path.is_dir() {
-                for entry in std::fs::read_dir(&path).unwrap().flatten() {
+                for entry in std::fs::read_dir(&path).into_iter().flatten().flatten() {
                     let path = entry.path();
 
                     if path.is_file() {
@@ -205,7 +202,7 @@ pub async fn detect(
 
     if let Ok(([build, latest], versions)) = api::mcjars::lookup(&file).await {
         if let Some(modpack_slug) = &config.modpack_slug {
-            let modpack = api::modrinth::project(modpack_slug).await.unwrap();
+            let modpack = api::modrinth::project(modpack_slug).await.ok()?;
 
             return Some(([build, latest], versions, Some(modpack)));
         }
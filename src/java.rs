@@ -1,4 +1,5 @@
 use crate::api::{self, Progress};
+use crate::error::Error;
 
 use colored::Colorize;
 use dirs::home_dir;
@@ -116,7 +117,7 @@ pub fn find_local() -> Option<(u8, String, String)> {
     None
 }
 
-pub async fn binary(version: u8) -> [String; 2] {
+pub async fn binary(version: u8) -> Result<[String; 2], Error> {
     println!(
         "{} {} {}",
         "checking for java".bright_black(),
@@ -138,7 +139,7 @@ pub async fn binary(version: u8) -> [String; 2] {
             "DONE".green().bold()
         );
 
-        return [path, root];
+        return Ok([path, root]);
     }
 
     if !installed.iter().any(|(v, _)| *v == version) {
@@ -149,7 +150,7 @@ pub async fn binary(version: u8) -> [String; 2] {
             "not found, installing...".bright_black()
         );
 
-        install(version).await.unwrap();
+        install(version).await?;
 
         println!(
             "{} {} {} {}",
@@ -168,13 +169,13 @@ pub async fn binary(version: u8) -> [String; 2] {
         "DONE".green().bold()
     );
 
-    [
+    Ok([
         format!("{}/{}/bin/java", *LOCATION, version),
         format!("{}/{}", *LOCATION, version),
-    ]
+    ])
 }
 
-pub async fn install(version: u8) -> Result<(), anyhow::Error> {
+pub async fn install(version: u8) -> Result<(), Error> {
     let query_arch = std::env::consts::ARCH;
     let query_os = match std::env::consts::OS {
         "macos" => "mac",
@@ -209,7 +210,11 @@ pub async fn install(version: u8) -> Result<(), anyhow::Error> {
 
     let binary = match binary {
         Some(binary) => binary,
-        None => return Err(anyhow::anyhow!("no binary found for Java {version}")),
+        None => {
+            return Err(Error::not_found(format!(
+                "no binary found for java {version}"
+            )));
+        }
     };
 
     let destination = format!("{}/{}/java.archive", *LOCATION, version);
@@ -261,10 +266,10 @@ pub async fn install(version: u8) -> Result<(), anyhow::Error> {
     );
 
     if binary.binary.package.name.ends_with(".zip") {
-        let mut archive = ZipArchive::new(File::open(&destination).unwrap()).unwrap();
+        let mut archive = ZipArchive::new(File::open(&destination)?)?;
         archive.extract(format!("{}/{}", *LOCATION, version))?;
     } else {
-        let mut archive = TarArchive::new(GzDecoder::new(File::open(&destination).unwrap()));
+        let mut archive = TarArchive::new(GzDecoder::new(File::open(&destination)?));
         archive.unpack(format!("{}/{}", *LOCATION, version))?;
     }
 
@@ -272,11 +277,9 @@ pub async fn install(version: u8) -> Result<(), anyhow::Error> {
 
     let entries = std::fs::read_dir(format!("{}/{}", *LOCATION, version))?;
     if entries.count() == 1 {
-        let entry = std::fs::read_dir(format!("{}/{}", *LOCATION, version))
-            .unwrap()
+        let entry = std::fs::read_dir(format!("{}/{}", *LOCATION, version))?
             .next()
-            .unwrap()
-            .unwrap();
+            .unwrap()?;
         let path = entry.path();
 
         for file in std::fs::read_dir(&path)?.flatten() {
@@ -307,18 +310,17 @@ pub async fn install(version: u8) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
-pub async fn versions() -> Vec<u8> {
+pub async fn versions() -> Result<Vec<u8>, Error> {
     let res = api::CLIENT
         .get("https://api.adoptium.net/v3/info/available_releases")
         .send()
-        .await
-        .unwrap();
-    let data = res.json::<ApiResponse>().await.unwrap();
+        .await?;
+    let data = res.json::<ApiResponse>().await?;
 
     #[derive(Deserialize)]
     struct ApiResponse {
         available_releases: Vec<u8>,
     }
 
-    data.available_releases
+    Ok(data.available_releases)
 }
@@ -0,0 +1,213 @@
+use crate::config::LimitsConfig;
+
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// The cpus `cpuAffinity` can contain, the size of `cpu_set_t`.
+pub const MAX_CPUS: usize = 1024;
+
+/// The cgroup mcvcli moves itself to when its own cgroup cannot have controllers enabled.
+const SUPERVISOR_CGROUP: &str = "mcvcli-supervisors";
+
+/// Applies the nice value, cpu affinity and `RLIMIT_NOFILE` to the spawned process.
+pub fn apply(command: &mut Command, limits: &LimitsConfig) {
+    let nice = limits.nice;
+    let cpu_affinity = limits.cpu_affinity.clone();
+    let nofile = limits.nofile;
+
+    if nice.is_none() && cpu_affinity.is_empty() && nofile.is_none() {
+        return;
+    }
+
+    // runs in the forked child, only syscalls from here on
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(nice) = nice
+                && libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0
+            {
+                return Err(std::io::Error::last_os_error());
+            }
+
+            #[cfg(target_os = "linux")]
+            if !cpu_affinity.is_empty() {
+                let mut set: libc::cpu_set_t = std::mem::zeroed();
+                // validated when the config is loaded, CPU_SET panics on larger cpus
+                for cpu in cpu_affinity.iter().filter(|cpu| **cpu < MAX_CPUS) {
+                    libc::CPU_SET(*cpu, &mut set);
+                }
+
+                if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+
+            if let Some(nofile) = nofile {
+                let mut limit: libc::rlimit = std::mem::zeroed();
+                if libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+
+                // raising the hard limit needs privileges, lowering it does not
+                limit.rlim_cur = nofile as libc::rlim_t;
+                limit.rlim_max = limit.rlim_max.max(nofile as libc::rlim_t);
+
+                if libc::setrlimit(libc::RLIMIT_NOFILE, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+
+            Ok(())
+        });
+    }
+}
+
+/// A cgroup v2 below the cgroup of mcvcli holding the java process, removed on drop.
+pub struct Cgroup {
+    path: PathBuf,
+}
+
+impl Cgroup {
+    /// Creates the cgroup with the memory and cpu limits, `None` if there are no limits.
+    pub fn create(name: &str, limits: &LimitsConfig) -> Result<Option<Self>, String> {
+        if limits.memory_max_mb.is_none() && limits.cpu_max_percent.is_none() {
+            return Ok(None);
+        }
+
+        // units of `mcvcli service install` apply these limits themselves (MemoryMax=, CPUQuota=)
+        if std::env::var_os("MCVCLI_SERVICE").is_some() {
+            return Ok(None);
+        }
+        if std::env::var_os("INVOCATION_ID").is_some() {
+            return Err(
+                "running in a systemd unit not installed by `mcvcli service install`, set MemoryMax= and CPUQuota= on the unit instead"
+                    .to_string(),
+            );
+        }
+
+        // resolved once, mcvcli moves itself into a leaf cgroup below it on the first start
+        static PARENT: std::sync::OnceLock<Result<PathBuf, String>> = std::sync::OnceLock::new();
+        let parent = PARENT.get_or_init(parent).clone()?;
+
+        let mut controllers = Vec::new();
+        if limits.memory_max_mb.is_some() {
+            controllers.push("+memory");
+        }
+        if limits.cpu_max_percent.is_some() {
+            controllers.push("+cpu");
+        }
+
+        enable_controllers(&parent, &controllers.join(" ")).map_err(|err| {
+            format!(
+                "unable to enable the {} cgroup controllers in {}: {err}",
+                controllers.join(" "),
+                parent.display()
+            )
+        })?;
+
+        let cgroup = Self {
+            path: parent.join(name),
+        };
+        std::fs::create_dir_all(&cgroup.path)
+            .map_err(|err| format!("unable to create {}: {err}", cgroup.path.display()))?;
+
+        if let Some(memory_max_mb) = limits.memory_max_mb {
+            cgroup.write(
+                "memory.max",
+                &memory_max_mb.saturating_mul(1024 * 1024).to_string(),
+            )?;
+        }
+        if let Some(cpu_max_percent) = limits.cpu_max_percent {
+            cgroup.write(
+                "cpu.max",
+                &format!("{} 100000", u64::from(cpu_max_percent) * 1000),
+            )?;
+        }
+
+        Ok(Some(cgroup))
+    }
+
+    fn write(&self, file: &str, value: &str) -> Result<(), String> {
+        let path = self.path.join(file);
+
+        std::fs::write(&path, value)
+            .map_err(|err| format!("unable to write {}: {err}", path.display()))
+    }
+
+    #[inline]
+    pub fn add(&self, pid: u32) -> Result<(), String> {
+        self.write("cgroup.procs", &pid.to_string())
+    }
+}
+
+/// The cgroup v2 mcvcli was started in.
+fn parent() -> Result<PathBuf, String> {
+    let own = std::fs::read_to_string("/proc/self/cgroup")
+        .ok()
+        .and_then(|cgroup| {
+            cgroup
+                .lines()
+                .find_map(|line| line.strip_prefix("0::").map(str::to_string))
+        })
+        .ok_or("cgroup v2 is not available")?;
+
+    // usually /sys/fs/cgroup, /sys/fs/cgroup/unified on hybrid systems
+    let mount = std::fs::read_to_string("/proc/mounts")
+        .ok()
+        .and_then(|mounts| {
+            mounts.lines().find_map(|line| {
+                let mut fields = line.split_whitespace();
+                let mount = fields.nth(1)?;
+
+                (fields.next()? == "cgroup2").then(|| mount.to_string())
+            })
+        })
+        .ok_or("cgroup v2 is not available")?;
+
+    let parent = PathBuf::from(mount).join(own.trim_start_matches('/'));
+    if !parent.join("cgroup.controllers").exists() {
+        return Err("cgroup v2 is not available".to_string());
+    }
+
+    Ok(parent)
+}
+
+/// Enables the controllers for the children of `parent`. cgroup v2 refuses that (EBUSY)
+/// while `parent` holds processes itself, so mcvcli moves itself into a leaf cgroup first.
+fn enable_controllers(parent: &Path, controllers: &str) -> std::io::Result<()> {
+    let subtree_control = parent.join("cgroup.subtree_control");
+
+    match std::fs::write(&subtree_control, controllers) {
+        Err(err) if err.raw_os_error() == Some(libc::EBUSY) => {
+            let leaf = parent.join(SUPERVISOR_CGROUP);
+            std::fs::create_dir_all(&leaf)?;
+            std::fs::write(leaf.join("cgroup.procs"), std::process::id().to_string())?;
+
+            std::fs::write(&subtree_control, controllers).map_err(|err| {
+                if err.raw_os_error() == Some(libc::EBUSY) {
+                    std::io::Error::other(
+                        "other processes share the cgroup of mcvcli, use `mcvcli service install`",
+                    )
+                } else {
+                    err
+                }
+            })
+        }
+        result => result,
+    }
+}
+
+impl Drop for Cgroup {
+    fn drop(&mut self) {
+        // children the server left behind keep the cgroup busy, cgroup.kill needs linux 5.14
+        if std::fs::remove_dir(&self.path).is_err()
+            && std::fs::write(self.path.join("cgroup.kill"), "1").is_ok()
+        {
+            for _ in 0..20 {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                if std::fs::remove_dir(&self.path).is_ok() {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use flate2::read::GzDecoder;
+use regex::Regex;
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+pub const DIRECTORY: &str = "logs";
+pub const LATEST: &str = "logs/latest.log";
+
+/// `[12:00:00] [Server thread/INFO]: ...` (vanilla) and
+/// `[18Oct2026 12:00:00.000] [Server thread/INFO] [net.minecraft.server.MinecraftServer/]: ...` (forge)
+static THREAD_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\[(?:(\d{2}[A-Za-z]{3}\d{4}) )?(\d{2}:\d{2}:\d{2})(?:\.\d+)?\] \[(.+?)/([A-Z]+)\](?: \[[^\]]*\])?: ?(.*)$",
+    )
+    .unwrap()
+});
+
+/// `[12:00:00 INFO]: ...` (paper console)
+static SHORT_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[(\d{2}:\d{2}:\d{2}) ([A-Z]+)\]: ?(.*)$").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(level: &str) -> Result<Self, Self::Err> {
+        match level.to_uppercase().as_str() {
+            "TRACE" => Ok(Self::Trace),
+            "DEBUG" | "FINE" => Ok(Self::Debug),
+            "INFO" => Ok(Self::Info),
+            "WARN" | "WARNING" => Ok(Self::Warn),
+            "ERROR" | "SEVERE" => Ok(Self::Error),
+            "FATAL" => Ok(Self::Fatal),
+            _ => Err(format!("unknown log level {level}")),
+        }
+    }
+}
+
+/// A single log entry, lines without a header (e.g. stack traces) are kept in `lines`.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub time: Option<NaiveDateTime>,
+    pub thread: Option<String>,
+    pub level: Option<Level>,
+    pub message: String,
+    pub lines: Vec<String>,
+}
+
+type Stamp = (Option<NaiveDate>, NaiveTime);
+
+struct Header {
+    date: Option<NaiveDate>,
+    time: NaiveTime,
+    thread: Option<String>,
+    level: Option<Level>,
+    message: String,
+}
+
+fn parse_header(line: &str) -> Option<Header> {
+    if let Some(captures) = THREAD_LINE.captures(line) {
+        return Some(Header {
+            date: captures
+                .get(1)
+                .and_then(|date| NaiveDate::parse_from_str(date.as_str(), "%d%b%Y").ok()),
+            time: NaiveTime::parse_from_str(&captures[2], "%H:%M:%S").ok()?,
+            thread: Some(captures[3].to_string()),
+            level: captures[4].parse().ok(),
+            message: captures[5].to_string(),
+        });
+    }
+
+    if let Some(captures) = SHORT_LINE.captures(line) {
+        return Some(Header {
+            date: None,
+            time: NaiveTime::parse_from_str(&captures[1], "%H:%M:%S").ok()?,
+            thread: None,
+            level: captures[2].parse().ok(),
+            message: captures[3].to_string(),
+        });
+    }
+
+    None
+}
+
+/// Parses log lines into entries. Minecraft logs only contain the time of day, so the date
+/// is derived from `date` (the day the file was last written to) by walking backwards and
+/// going back a day whenever the time jumps forward.
+pub fn parse(content: &str, date: NaiveDate) -> Vec<Entry> {
+    // the date (if logged) and time of each entry, used to fill in `Entry::time` afterwards
+    let mut entries: Vec<(Option<Stamp>, Entry)> = Vec::new();
+
+    for line in content.lines() {
+        match parse_header(line) {
+            Some(header) => entries.push((
+                Some((header.date, header.time)),
+                Entry {
+                    time: None,
+                    thread: header.thread,
+                    level: header.level,
+                    message: header.message,
+                    lines: vec![line.to_string()],
+                },
+            )),
+            None => match entries.last_mut() {
+                Some((_, entry)) => entry.lines.push(line.to_string()),
+                None => entries.push((
+                    None,
+                    Entry {
+                        time: None,
+                        thread: None,
+                        level: None,
+                        message: line.to_string(),
+                        lines: vec![line.to_string()],
+                    },
+                )),
+            },
+        }
+    }
+
+    let mut date = date;
+    let mut next: Option<NaiveTime> = None;
+
+    for (header, entry) in entries.iter_mut().rev() {
+        let Some((header_date, time)) = *header else {
+            continue;
+        };
+
+        if let Some(header_date) = header_date {
+            date = header_date;
+        } else if next.is_some_and(|next| time > next) {
+            date = date.pred_opt().unwrap_or(date);
+        }
+
+        entry.time = Some(date.and_time(time));
+        next = Some(time);
+    }
+
+    entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Parses a time filter: a duration ago (`30m`, `2h`), a date, a date and time or a time of today.
+pub fn parse_time(time: &str) -> Result<NaiveDateTime, String> {
+    if let Ok(duration) = humantime::parse_duration(time) {
+        return Ok(Local::now().naive_local()
+            - TimeDelta::from_std(duration).map_err(|err| err.to_string())?);
+    }
+
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(time, format) {
+            return Ok(time);
+        }
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(time, "%Y-%m-%d") {
+        return Ok(date.and_time(NaiveTime::MIN));
+    }
+
+    for format in ["%H:%M:%S", "%H:%M"] {
+        if let Ok(time) = NaiveTime::parse_from_str(time, format) {
+            return Ok(Local::now().date_naive().and_time(time));
+        }
+    }
+
+    Err(format!(
+        "invalid time {time}, use e.g. `30m`, `2025-01-01`, `2025-01-01 12:00` or `12:00`"
+    ))
+}
+
+/// Lists the rotated log files (`logs/2025-01-01-1.log.gz`) with their date, oldest first.
+pub fn archives() -> Vec<(NaiveDate, PathBuf)> {
+    let mut archives = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(DIRECTORY) {
+        for entry in entries.flatten() {
+            if let Some((date, index)) = archive(&entry.file_name().to_string_lossy()) {
+                archives.push((date, index, entry.path()));
+            }
+        }
+    }
+
+    archives.sort();
+
+    archives
+        .into_iter()
+        .map(|(date, _, path)| (date, path))
+        .collect()
+}
+
+pub fn read(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut bytes = Vec::new();
+
+    if path.extension().is_some_and(|extension| extension == "gz") {
+        GzDecoder::new(file).read_to_end(&mut bytes)?;
+    } else {
+        file.read_to_end(&mut bytes)?;
+    }
+
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// The day a log file was last written to.
+/// The date and index of a rotated log file name (`2025-01-01-1.log.gz`).
+fn archive(file_name: &str) -> Option<(NaiveDate, u32)> {
+    let name = file_name.strip_suffix(".log.gz")?;
+    let (date, index) = name.rsplit_once('-').unwrap_or((name, "0"));
+
+    Some((
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?,
+        index.parse().unwrap_or(0),
+    ))
+}
+
+/// The day a log file was last written to. Rotated logs are compressed on the next start,
+/// days later maybe, so their date comes from the file name instead of the mtime.
+pub fn date(path: &Path) -> NaiveDate {
+    if let Some((date, _)) = path
+        .file_name()
+        .and_then(|file_name| archive(&file_name.to_string_lossy()))
+    {
+        return date;
+    }
+
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(|modified| chrono::DateTime::<Local>::from(modified).date_naive())
+        .unwrap_or_else(|_| Local::now().date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn time(date: NaiveDate, time: &str) -> Option<NaiveDateTime> {
+        Some(date.and_time(NaiveTime::parse_from_str(time, "%H:%M:%S").unwrap()))
+    }
+
+    #[test]
+    fn parses_vanilla_header() {
+        let entries = parse(
+            "[12:00:00] [Server thread/INFO]: Steve joined the game",
+            date(2026, 1, 2),
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].time, time(date(2026, 1, 2), "12:00:00"));
+        assert_eq!(entries[0].thread.as_deref(), Some("Server thread"));
+        assert_eq!(entries[0].level, Some(Level::Info));
+        assert_eq!(entries[0].message, "Steve joined the game");
+    }
+
+    #[test]
+    fn parses_forge_header_with_date() {
+        let entries = parse(
+            "[18Oct2025 12:00:00.123] [Server thread/WARN] [net.minecraft.server.MinecraftServer/]: Can't keep up!",
+            date(2026, 1, 2),
+        );
+
+        assert_eq!(entries[0].time, time(date(2025, 10, 18), "12:00:00"));
+        assert_eq!(entries[0].level, Some(Level::Warn));
+        assert_eq!(entries[0].message, "Can't keep up!");
+    }
+
+    #[test]
+    fn parses_paper_console_header() {
+        let entries = parse("[12:00:00 ERROR]: boom", date(2026, 1, 2));
+
+        assert_eq!(entries[0].time, time(date(2026, 1, 2), "12:00:00"));
+        assert_eq!(entries[0].thread, None);
+        assert_eq!(entries[0].level, Some(Level::Error));
+        assert_eq!(entries[0].message, "boom");
+    }
+
+    #[test]
+    fn keeps_continuation_lines_with_their_entry() {
+        let entries = parse(
+            "[12:00:00] [Server thread/ERROR]: crashed\njava.lang.Exception\n\tat Main.main",
+            date(2026, 1, 2),
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].lines.len(), 3);
+    }
+
+    #[test]
+    fn walks_dates_back_over_midnight() {
+        let entries = parse(
+            "[22:00:00] [Server thread/INFO]: a\n[23:59:59] [Server thread/INFO]: b\n[00:00:01] [Server thread/INFO]: c\n[23:00:00] [Server thread/INFO]: d\n[01:00:00] [Server thread/INFO]: e",
+            date(2026, 1, 3),
+        );
+
+        let times = entries.iter().map(|entry| entry.time).collect::<Vec<_>>();
+        assert_eq!(
+            times,
+            [
+                time(date(2026, 1, 1), "22:00:00"),
+                time(date(2026, 1, 1), "23:59:59"),
+                time(date(2026, 1, 2), "00:00:01"),
+                time(date(2026, 1, 2), "23:00:00"),
+                time(date(2026, 1, 3), "01:00:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_archive_names() {
+        assert_eq!(archive("2025-01-01-3.log.gz"), Some((date(2025, 1, 1), 3)));
+        assert_eq!(archive("latest.log"), None);
+        assert_eq!(archive("debug-1.log.gz"), None);
+        assert_eq!(
+            super::date(Path::new("logs/2025-01-01-1.log.gz")),
+            date(2025, 1, 1)
+        );
+    }
+
+    #[test]
+    fn parses_absolute_times() {
+        assert_eq!(
+            parse_time("2025-01-01 12:30").ok(),
+            time(date(2025, 1, 1), "12:30:00")
+        );
+        assert_eq!(
+            parse_time("2025-01-01").ok(),
+            time(date(2025, 1, 1), "00:00:00")
+        );
+        assert!(parse_time("yesterday-ish").is_err());
+    }
+}
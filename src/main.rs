@@ -1,14 +1,34 @@
 mod api;
+mod backup;
 mod commands;
 mod config;
+mod crash;
+mod dashboard;
 mod detached;
+mod error;
+mod hooks;
+mod http;
 mod jar;
 mod java;
+mod limits;
+mod logs;
+mod metrics;
 mod modpack;
+mod players;
+mod preflight;
 mod profiles;
 mod progress;
+mod properties;
+mod rcon;
+mod registry;
+mod service;
+mod supervisor;
+mod tokens;
+
+use crate::error::Error;
 
 use clap::{Arg, Command};
+use colored::Colorize;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -19,7 +39,24 @@ fn cli() -> Command {
         .arg_required_else_help(true)
         .allow_external_subcommands(true)
         .version(VERSION)
-
+        .arg(
+            Arg::new("dir")
+                .long("dir")
+                .short('C')
+                .help("The server directory to run in (defaults to the nearest directory with a .mcvcli.json)")
+                .num_args(1)
+                .global(true)
+                .required(false),
+        )
+        .arg(
+            Arg::new("server")
+                .long("server")
+                .help("The registered server to run in (see `mcvcli ls`)")
+                .num_args(1)
+                .global(true)
+                .conflicts_with("dir")
+                .required(false),
+        )
         .subcommand(
             Command::new("init")
                 .about("Initializes a new Minecraft server")
@@ -68,7 +105,10 @@ fn cli() -> Command {
                         .long("ram")
                         .short('r')
                         .num_args(1)
-                        .value_parser(clap::value_parser!(u16).range(1024..=49152))
+                        .value_parser(
+                            clap::value_parser!(u32)
+                                .range(config::MIN_RAM_MB as i64..=config::MAX_RAM_MB as i64),
+                        )
                         .required(false),
                 )
                 .arg(
@@ -99,7 +139,10 @@ fn cli() -> Command {
                         .short('r')
                         .help("The amount of RAM to allocate to the server (in MB)")
                         .num_args(1)
-                        .value_parser(clap::value_parser!(u16).range(1024..=49152))
+                        .value_parser(
+                            clap::value_parser!(u32)
+                                .range(config::MIN_RAM_MB as i64..=config::MAX_RAM_MB as i64),
+                        )
                         .required(false),
                 )
                 .arg(
@@ -110,6 +153,14 @@ fn cli() -> Command {
                         .num_args(1)
                         .required(false),
                 )
+                .arg(
+                    Arg::new("stop_transport")
+                        .long("stop-transport")
+                        .help("How the stop command is sent to the server")
+                        .num_args(1)
+                        .value_parser(["console", "rcon"])
+                        .required(false),
+                )
                 .arg(
                     Arg::new("flags")
                         .long("flags")
@@ -126,6 +177,36 @@ fn cli() -> Command {
                         .num_args(1)
                         .required(false),
                 )
+                .arg(
+                    Arg::new("restart")
+                        .long("restart")
+                        .help("The restart policy to use when the server exits")
+                        .num_args(1)
+                        .value_parser(["never", "on-failure", "always"])
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("watchdog")
+                        .long("watchdog")
+                        .help("Restart the server when it stops answering pings")
+                        .num_args(1)
+                        .value_parser(["on", "off"])
+                        .required(false),
+                )
+                .args_conflicts_with_subcommands(true)
+                .subcommand(
+                    Command::new("validate")
+                        .about("Checks the configuration file for unknown keys and invalid values")
+                        .arg(
+                            Arg::new("profile")
+                                .long("profile")
+                                .short('p')
+                                .help("The profile to validate")
+                                .num_args(1)
+                                .required(false),
+                        )
+                        .arg_required_else_help(false),
+                )
                 .arg_required_else_help(false),
         )
         .subcommand(
@@ -208,6 +289,46 @@ fn cli() -> Command {
                         .value_parser(clap::value_parser!(u64).range(1..))
                         .required(false),
                 )
+                .arg(
+                    Arg::new("wait_ready")
+                        .long("wait-ready")
+                        .short('w')
+                        .help("Wait until the detached server has finished starting")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .requires("detached")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("ready_timeout")
+                        .long("ready-timeout")
+                        .help("The amount of time to wait for the server to be ready (seconds)")
+                        .num_args(1)
+                        .default_value("300")
+                        .value_parser(clap::value_parser!(u64).range(1..))
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("dashboard")
+                        .long("dashboard")
+                        .help("Show the terminal dashboard instead of the plain console")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .conflicts_with("detached")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .short('f')
+                        .help("Start even if a port is in use or memory or disk space is low")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .required(false),
+                )
                 .arg_required_else_help(false),
         )
         .subcommand(
@@ -223,16 +344,374 @@ fn cli() -> Command {
                         .value_parser(clap::value_parser!(u64).range(1..))
                         .required(false),
                 )
+                .arg(
+                    Arg::new("countdown")
+                        .long("countdown")
+                        .short('c')
+                        .help("Warn players in-game and wait this long before stopping (e.g., `5m`)")
+                        .num_args(1)
+                        .value_parser(humantime::parse_duration)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("abort")
+                        .long("abort")
+                        .short('a')
+                        .help("Cancel a stop or restart countdown that is in progress")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .conflicts_with("countdown")
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("restart")
+                .about("Restarts the Minecraft server")
+                .arg(
+                    Arg::new("countdown")
+                        .long("countdown")
+                        .short('c')
+                        .help("Warn players in-game and wait this long before restarting (e.g., `5m`)")
+                        .num_args(1)
+                        .value_parser(humantime::parse_duration)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("abort")
+                        .long("abort")
+                        .short('a')
+                        .help("Cancel a stop or restart countdown that is in progress")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .conflicts_with("countdown")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("ready_timeout")
+                        .long("ready-timeout")
+                        .help("The amount of time to wait for the server to be ready again (seconds)")
+                        .num_args(1)
+                        .default_value("300")
+                        .value_parser(clap::value_parser!(u64).range(1..))
+                        .required(false),
+                )
                 .arg_required_else_help(false),
         )
+        .subcommand(
+            Command::new("supervise")
+                .about("Runs the server supervisor (used internally by detached mode)")
+                .hide(true)
+                .arg(
+                    Arg::new("identifier")
+                        .long("identifier")
+                        .num_args(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("java")
+                        .long("java")
+                        .num_args(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("java_home")
+                        .long("java-home")
+                        .num_args(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("timeout")
+                        .long("timeout")
+                        .num_args(1)
+                        .default_value("20")
+                        .value_parser(clap::value_parser!(u64).range(1..))
+                        .required(false),
+                ),
+        )
         .subcommand(
             Command::new("attach")
                 .about("Attaches to the Minecraft server console")
+                .arg(
+                    Arg::new("tail")
+                        .long("tail")
+                        .short('n')
+                        .help("The amount of logged console lines to replay before attaching")
+                        .num_args(1)
+                        .default_value("20")
+                        .value_parser(clap::value_parser!(usize))
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("crash")
+                .about("Analyzes a crash report and looks for the mods that caused it")
+                .arg(
+                    Arg::new("report")
+                        .help("The crash report to analyze (`latest` or a file in crash-reports)")
+                        .num_args(1)
+                        .default_value("latest")
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("logs")
+                .about("Shows the logs of the Minecraft server")
+                .arg(
+                    Arg::new("follow")
+                        .long("follow")
+                        .short('f')
+                        .help("Keep printing new lines as they are written")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("since")
+                        .long("since")
+                        .short('s')
+                        .help("Only show entries after this time (e.g., `30m`, `2025-01-01`, `2025-01-01 12:00`, `12:00`)")
+                        .num_args(1)
+                        .value_parser(logs::parse_time)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("until")
+                        .long("until")
+                        .short('u')
+                        .help("Only show entries before this time")
+                        .num_args(1)
+                        .value_parser(logs::parse_time)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("level")
+                        .long("level")
+                        .short('l')
+                        .help("Only show entries of this level or above (e.g., `WARN`, `ERROR`)")
+                        .num_args(1)
+                        .value_parser(|level: &str| level.parse::<logs::Level>())
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("thread")
+                        .long("thread")
+                        .short('t')
+                        .help("Only show entries logged by a thread containing this name (e.g., `Server thread`)")
+                        .num_args(1)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("grep")
+                        .long("grep")
+                        .short('g')
+                        .help("Only show entries whose message matches this regex")
+                        .num_args(1)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("lines")
+                        .long("lines")
+                        .short('n')
+                        .help("Only show the last n matching entries")
+                        .num_args(1)
+                        .value_parser(clap::value_parser!(usize))
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .short('a')
+                        .help("Also read the rotated logs (logs/*.log.gz), implied by --since and --until")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("exec")
+                .about("Runs a command on the Minecraft server and prints its console output (exits with 10 when --until times out)")
+                .arg(
+                    Arg::new("command")
+                        .help("The command to run")
+                        .num_args(1..)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("timeout")
+                        .long("timeout")
+                        .short('t')
+                        .help("How long to collect console output for (e.g., `2s`, `500ms`)")
+                        .num_args(1)
+                        .default_value("2s")
+                        .value_parser(humantime::parse_duration)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("until")
+                        .long("until")
+                        .short('u')
+                        .help("Stop collecting output once a line matches this regex")
+                        .num_args(1)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .short('j')
+                        .help("Print the output as JSON")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .required(false),
+                )
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Serves a REST and WebSocket API for the server, guarded by bearer tokens")
+                .arg(
+                    Arg::new("listen")
+                        .long("listen")
+                        .short('l')
+                        .help("The address to listen on")
+                        .num_args(1)
+                        .default_value("127.0.0.1:25580")
+                        .value_parser(clap::value_parser!(std::net::SocketAddr))
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("metrics")
+                .about("Serves Prometheus metrics of the server on /metrics")
+                .arg(
+                    Arg::new("listen")
+                        .long("listen")
+                        .short('l')
+                        .help("The address to listen on")
+                        .num_args(1)
+                        .default_value("127.0.0.1:9225")
+                        .value_parser(clap::value_parser!(std::net::SocketAddr))
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .short('a')
+                        .help("Export every registered server instead of the current one")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("token")
+                .about("Manages the bearer tokens of the API")
+                .subcommand(
+                    Command::new("list")
+                        .about("Lists all tokens")
+                        .arg_required_else_help(false),
+                )
+                .subcommand(
+                    Command::new("create")
+                        .about("Creates a token")
+                        .arg(
+                            Arg::new("name")
+                                .help("The name of the token")
+                                .num_args(1)
+                                .required(true),
+                        )
+                        .arg_required_else_help(true),
+                )
+                .subcommand(
+                    Command::new("revoke")
+                        .about("Revokes a token")
+                        .arg(
+                            Arg::new("name")
+                                .help("The name of the token")
+                                .num_args(1)
+                                .required(true),
+                        )
+                        .arg_required_else_help(true),
+                )
+                .arg_required_else_help(true)
+                .subcommand_required(true),
+        )
+        .subcommand(
+            Command::new("dashboard")
+                .about("Opens a terminal dashboard with the console, resource graphs and players (when detached)")
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("ls")
+                .about("Lists all registered servers of this machine")
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .short('j')
+                        .help("Print the servers as JSON")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("register")
+                .about("Registers the server so it shows up in `mcvcli ls` and works with --server")
+                .arg(
+                    Arg::new("name")
+                        .help("The name of the server (defaults to the directory name)")
+                        .num_args(1)
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
+        .subcommand(
+            Command::new("unregister")
+                .about("Removes a server from the registry (keeps its files)")
+                .arg(
+                    Arg::new("name")
+                        .help("The name of the server (defaults to the current server)")
+                        .num_args(1)
+                        .required(false),
+                )
                 .arg_required_else_help(false),
         )
         .subcommand(
             Command::new("status")
                 .about("Gets the status of the Minecraft server (when detached)")
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .short('j')
+                        .help("Print the status as JSON")
+                        .num_args(0)
+                        .default_value("false")
+                        .value_parser(clap::value_parser!(bool))
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("sample")
+                        .long("sample")
+                        .short('s')
+                        .help("How long to sample the cpu usage for (e.g., `1s`, `500ms`)")
+                        .num_args(1)
+                        .default_value("1s")
+                        .value_parser(humantime::parse_duration)
+                        .required(false),
+                )
                 .arg_required_else_help(false),
         )
         .subcommand(
@@ -266,6 +745,34 @@ fn cli() -> Command {
                         .required(false),
                 )
         )
+        .subcommand(
+            Command::new("rcon")
+                .about("Runs commands on the Minecraft server over RCON")
+                .arg(
+                    Arg::new("command")
+                        .help("The command to run, starts an interactive session if omitted")
+                        .num_args(1..)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("address")
+                        .long("address")
+                        .short('a')
+                        .help("The RCON address of the server (e.g., `example.com:25575`), defaults to server.properties")
+                        .num_args(1)
+                        .value_parser(commands::rcon::parse_address)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("password")
+                        .long("password")
+                        .short('p')
+                        .help("The RCON password of the server, defaults to server.properties")
+                        .num_args(1)
+                        .required(false),
+                )
+                .arg_required_else_help(false),
+        )
         .subcommand(
             Command::new("version")
                 .about("Gets the installed version of the Minecraft server")
@@ -362,6 +869,280 @@ fn cli() -> Command {
                 .arg_required_else_help(true)
                 .subcommand_required(true),
         )
+        .subcommand(
+            Command::new("players")
+                .about("Shows the players of the server, tracked from the console and logs")
+                .subcommand(
+                    Command::new("online")
+                        .about("Lists the players that are currently online")
+                        .arg(
+                            Arg::new("json")
+                                .long("json")
+                                .short('j')
+                                .help("Print the output as JSON")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg_required_else_help(false),
+                )
+                .subcommand(
+                    Command::new("history")
+                        .about("Shows the playtime, sessions and deaths of a player")
+                        .arg(
+                            Arg::new("name")
+                                .help("The name of the player")
+                                .num_args(1)
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("limit")
+                                .long("limit")
+                                .short('l')
+                                .help("The amount of recent sessions and deaths to show")
+                                .num_args(1)
+                                .default_value("10")
+                                .value_parser(clap::value_parser!(usize))
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("json")
+                                .long("json")
+                                .short('j')
+                                .help("Print the output as JSON")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg_required_else_help(true),
+                )
+                .subcommand(
+                    Command::new("sessions")
+                        .about("Lists the sessions of all players")
+                        .arg(
+                            Arg::new("since")
+                                .long("since")
+                                .short('s')
+                                .help("Only show sessions since this time (e.g., `7d`, `2h`, `2025-01-01`)")
+                                .num_args(1)
+                                .default_value("7d")
+                                .value_parser(logs::parse_time)
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("json")
+                                .long("json")
+                                .short('j')
+                                .help("Print the output as JSON")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg_required_else_help(false),
+                )
+                .arg_required_else_help(true)
+                .subcommand_required(true),
+        )
+        .subcommand(
+            Command::new("schedule")
+                .about("Manages scheduled tasks of the server")
+                .subcommand(
+                    Command::new("list")
+                        .about("Lists all scheduled tasks")
+                        .arg_required_else_help(false),
+                )
+                .subcommand(
+                    Command::new("add")
+                        .about("Adds a scheduled task")
+                        .arg(
+                            Arg::new("name")
+                                .help("The name of the scheduled task")
+                                .num_args(1)
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("cron")
+                                .help("The cron expression of the scheduled task (e.g., `0 4 * * *`)")
+                                .num_args(1)
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("command")
+                                .long("command")
+                                .short('c')
+                                .help("The console command to run")
+                                .num_args(1)
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("restart")
+                                .long("restart")
+                                .short('r')
+                                .help("Restart the server")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("backup")
+                                .long("backup")
+                                .short('b')
+                                .help("Create a backup of the server")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("warnings")
+                                .long("warnings")
+                                .short('w')
+                                .help("The in-game warnings to broadcast before restarting (e.g., `5m,1m,30s`)")
+                                .num_args(1)
+                                .value_delimiter(',')
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("keep")
+                                .long("keep")
+                                .short('k')
+                                .help("The amount of backups to keep")
+                                .num_args(1)
+                                .value_parser(clap::builder::RangedU64ValueParser::<usize>::new().range(1..))
+                                .required(false),
+                        )
+                        .arg_required_else_help(true),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about("Removes a scheduled task")
+                        .arg(
+                            Arg::new("name")
+                                .help("The name of the scheduled task to remove")
+                                .num_args(1)
+                                .required(false),
+                        )
+                        .arg_required_else_help(false),
+                )
+                .arg_required_else_help(true)
+                .subcommand_required(true),
+        )
+        .subcommand(
+            Command::new("service")
+                .about("Manages the systemd service of the server")
+                .subcommand(
+                    Command::new("install")
+                        .about("Installs a systemd unit that runs the server")
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .short('n')
+                                .help("The name of the unit (defaults to mcvcli-{directory name})")
+                                .num_args(1)
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("system")
+                                .long("system")
+                                .short('s')
+                                .help("Use a system unit instead of a user unit")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("user")
+                                .long("user")
+                                .short('u')
+                                .help("The user a system unit runs the server as (defaults to the user that invoked sudo)")
+                                .num_args(1)
+                                .requires("system")
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("output")
+                                .long("output")
+                                .short('o')
+                                .help("Write the unit to a file (or `-` for stdout) instead of installing it")
+                                .num_args(1)
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("timeout")
+                                .long("timeout")
+                                .short('t')
+                                .help("The amount of time to wait for the server to stop (seconds)")
+                                .num_args(1)
+                                .default_value("20")
+                                .value_parser(clap::value_parser!(u64).range(1..))
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("eula")
+                                .long("eula")
+                                .short('e')
+                                .help("Accept the Minecraft EULA automatically")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg_required_else_help(false),
+                )
+                .subcommand(
+                    Command::new("uninstall")
+                        .about("Stops and removes the systemd unit of the server")
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .short('n')
+                                .help("The name of the unit (defaults to mcvcli-{directory name})")
+                                .num_args(1)
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("system")
+                                .long("system")
+                                .short('s')
+                                .help("Use a system unit instead of a user unit")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg_required_else_help(false),
+                )
+                .subcommand(
+                    Command::new("status")
+                        .about("Shows the state of the systemd unit of the server")
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .short('n')
+                                .help("The name of the unit (defaults to mcvcli-{directory name})")
+                                .num_args(1)
+                                .required(false),
+                        )
+                        .arg(
+                            Arg::new("system")
+                                .long("system")
+                                .short('s')
+                                .help("Use a system unit instead of a user unit")
+                                .num_args(0)
+                                .default_value("false")
+                                .value_parser(clap::value_parser!(bool))
+                                .required(false),
+                        )
+                        .arg_required_else_help(false),
+                )
+                .arg_required_else_help(true)
+                .subcommand_required(true),
+        )
         .subcommand(
             Command::new("java")
                 .about("Manages Java versions")
@@ -413,79 +1194,134 @@ fn cli() -> Command {
 
 #[tokio::main]
 async fn main() {
+    std::panic::set_hook(Box::new(|info| {
+        eprintln!(
+            "{} {}",
+            "mcvcli ran into an internal error, please report this:".red(),
+            info.to_string().red()
+        );
+    }));
+
     let matches = cli().get_matches();
 
-    match matches.subcommand() {
-        Some(("init", sub_matches)) => {
-            std::process::exit(commands::init::init(sub_matches, None, None).await)
-        }
-        Some(("config", sub_matches)) => {
-            std::process::exit(commands::config::config(sub_matches).await)
-        }
-        Some(("install", sub_matches)) => {
-            std::process::exit(commands::install::install(sub_matches).await)
-        }
-        Some(("start", sub_matches)) => {
-            std::process::exit(commands::start::start(sub_matches).await)
-        }
-        Some(("stop", sub_matches)) => std::process::exit(commands::stop::stop(sub_matches).await),
-        Some(("attach", sub_matches)) => {
-            std::process::exit(commands::attach::attach(sub_matches).await)
-        }
-        Some(("status", sub_matches)) => {
-            std::process::exit(commands::status::status(sub_matches).await)
-        }
-        Some(("lookup", sub_matches)) => {
-            std::process::exit(commands::lookup::lookup(sub_matches).await)
-        }
-        Some(("query", sub_matches)) => {
-            std::process::exit(commands::query::query(sub_matches).await)
-        }
-        Some(("version", sub_matches)) => {
-            std::process::exit(commands::version::version(sub_matches).await)
+    let directory = match matches.get_one::<String>("server") {
+        Some(name) => match registry::Registry::load().get(name) {
+            Some(server) => Some(server.path.clone()),
+            None => {
+                let err = Error::not_found(format!("server `{name}` is not registered"));
+                err.print();
+                std::process::exit(err.code());
+            }
+        },
+        None => matches.get_one::<String>("dir").cloned(),
+    };
+
+    if let Some(directory) = directory {
+        if matches.subcommand_name() == Some("init") {
+            std::fs::create_dir_all(&directory).unwrap_or_default();
         }
-        Some(("update", sub_matches)) => {
-            std::process::exit(commands::update::update(sub_matches).await)
+
+        if let Err(err) = std::env::set_current_dir(&directory) {
+            let err = Error::io(format!("unable to use directory `{directory}` ({err})"));
+            err.print();
+            std::process::exit(err.code());
         }
-        Some(("profile", sub_matches)) => match sub_matches.subcommand() {
-            Some(("create", sub_matches)) => {
-                std::process::exit(commands::profile::create::create(sub_matches).await)
-            }
-            Some(("delete", sub_matches)) => {
-                std::process::exit(commands::profile::delete::delete(sub_matches).await)
-            }
-            Some(("use", sub_matches)) => {
-                std::process::exit(commands::profile::r#use::r#use(sub_matches).await)
-            }
-            Some(("list", sub_matches)) => {
-                std::process::exit(commands::profile::list::list(sub_matches).await)
+    } else if matches.subcommand_name() != Some("init")
+        && let Some(root) = std::env::current_dir()
+            .ok()
+            .and_then(|directory| config::discover(&directory))
+    {
+        std::env::set_current_dir(root).unwrap();
+    }
+
+    let result = match matches.subcommand() {
+        Some(("init", sub_matches)) => commands::init::init(sub_matches, None, None).await,
+        Some(("config", sub_matches)) => match sub_matches.subcommand() {
+            Some(("validate", sub_matches)) => {
+                commands::config::validate::validate(sub_matches).await
             }
+            _ => commands::config::config(sub_matches).await,
+        },
+        Some(("install", sub_matches)) => commands::install::install(sub_matches).await,
+        Some(("start", sub_matches)) => commands::start::start(sub_matches).await,
+        Some(("stop", sub_matches)) => commands::stop::stop(sub_matches).await,
+        Some(("restart", sub_matches)) => commands::restart::restart(sub_matches).await,
+        Some(("supervise", sub_matches)) => commands::supervise::supervise(sub_matches).await,
+        Some(("attach", sub_matches)) => commands::attach::attach(sub_matches).await,
+        Some(("crash", sub_matches)) => commands::crash::crash(sub_matches).await,
+        Some(("logs", sub_matches)) => commands::logs::logs(sub_matches).await,
+        Some(("exec", sub_matches)) => commands::exec::exec(sub_matches).await,
+        Some(("dashboard", sub_matches)) => commands::dashboard::dashboard(sub_matches).await,
+        Some(("serve", sub_matches)) => commands::serve::serve(sub_matches).await,
+        Some(("metrics", sub_matches)) => commands::metrics::metrics(sub_matches).await,
+        Some(("token", sub_matches)) => match sub_matches.subcommand() {
+            Some(("list", sub_matches)) => commands::token::list::list(sub_matches).await,
+            Some(("create", sub_matches)) => commands::token::create::create(sub_matches).await,
+            Some(("revoke", sub_matches)) => commands::token::revoke::revoke(sub_matches).await,
+            _ => unreachable!(),
+        },
+        Some(("ls", sub_matches)) => commands::ls::ls(sub_matches).await,
+        Some(("register", sub_matches)) => commands::register::register(sub_matches).await,
+        Some(("unregister", sub_matches)) => commands::unregister::unregister(sub_matches).await,
+        Some(("status", sub_matches)) => commands::status::status(sub_matches).await,
+        Some(("lookup", sub_matches)) => commands::lookup::lookup(sub_matches).await,
+        Some(("query", sub_matches)) => commands::query::query(sub_matches).await,
+        Some(("rcon", sub_matches)) => commands::rcon::rcon(sub_matches).await,
+        Some(("version", sub_matches)) => commands::version::version(sub_matches).await,
+        Some(("update", sub_matches)) => commands::update::update(sub_matches).await,
+        Some(("profile", sub_matches)) => match sub_matches.subcommand() {
+            Some(("create", sub_matches)) => commands::profile::create::create(sub_matches).await,
+            Some(("delete", sub_matches)) => commands::profile::delete::delete(sub_matches).await,
+            Some(("use", sub_matches)) => commands::profile::r#use::r#use(sub_matches).await,
+            Some(("list", sub_matches)) => commands::profile::list::list(sub_matches).await,
             _ => unreachable!(),
         },
         Some(("mods", sub_matches)) => match sub_matches.subcommand() {
-            Some(("list", sub_matches)) => {
-                std::process::exit(commands::mods::list::list(sub_matches).await)
-            }
-            Some(("delete", sub_matches)) => {
-                std::process::exit(commands::mods::delete::delete(sub_matches).await)
-            }
+            Some(("list", sub_matches)) => commands::mods::list::list(sub_matches).await,
+            Some(("delete", sub_matches)) => commands::mods::delete::delete(sub_matches).await,
             _ => unreachable!(),
         },
-        Some(("java", sub_matches)) => match sub_matches.subcommand() {
-            Some(("list", sub_matches)) => {
-                std::process::exit(commands::java::list::list(sub_matches).await)
+        Some(("players", sub_matches)) => match sub_matches.subcommand() {
+            Some(("online", sub_matches)) => commands::players::online::online(sub_matches).await,
+            Some(("history", sub_matches)) => {
+                commands::players::history::history(sub_matches).await
             }
-            Some(("use", sub_matches)) => {
-                std::process::exit(commands::java::r#use::r#use(sub_matches).await)
+            Some(("sessions", sub_matches)) => {
+                commands::players::sessions::sessions(sub_matches).await
             }
+            _ => unreachable!(),
+        },
+        Some(("schedule", sub_matches)) => match sub_matches.subcommand() {
+            Some(("list", sub_matches)) => commands::schedule::list::list(sub_matches).await,
+            Some(("add", sub_matches)) => commands::schedule::add::add(sub_matches).await,
+            Some(("remove", sub_matches)) => commands::schedule::remove::remove(sub_matches).await,
+            _ => unreachable!(),
+        },
+        Some(("service", sub_matches)) => match sub_matches.subcommand() {
             Some(("install", sub_matches)) => {
-                std::process::exit(commands::java::install::install(sub_matches).await)
+                commands::service::install::install(sub_matches).await
             }
-            Some(("delete", sub_matches)) => {
-                std::process::exit(commands::java::delete::delete(sub_matches).await)
+            Some(("uninstall", sub_matches)) => {
+                commands::service::uninstall::uninstall(sub_matches).await
             }
+            Some(("status", sub_matches)) => commands::service::status::status(sub_matches).await,
             _ => unreachable!(),
         },
-        _ => cli().print_help().unwrap(),
+        Some(("java", sub_matches)) => match sub_matches.subcommand() {
+            Some(("list", sub_matches)) => commands::java::list::list(sub_matches).await,
+            Some(("use", sub_matches)) => commands::java::r#use::r#use(sub_matches).await,
+            Some(("install", sub_matches)) => commands::java::install::install(sub_matches).await,
+            Some(("delete", sub_matches)) => commands::java::delete::delete(sub_matches).await,
+            _ => unreachable!(),
+        },
+        _ => cli().print_help().map_err(Error::from),
+    };
+
+    match result {
+        Ok(()) => std::process::exit(0),
+        Err(err) => {
+            err.print();
+            std::process::exit(err.code());
+        }
     }
 }
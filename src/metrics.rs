@@ -0,0 +1,377 @@
+use crate::config::Config;
+use crate::properties::{self, Properties};
+use crate::registry::{self, Registry};
+use crate::supervisor::client::Client;
+use crate::{api, detached};
+
+use indexmap::IndexMap;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// How long the installed and latest build ids are cached, looking them up hashes the jar.
+const BUILD_CACHE: Duration = Duration::from_secs(60 * 60);
+/// How long the world sizes are cached, walking a large world takes a while.
+const WORLD_CACHE: Duration = Duration::from_secs(5 * 60);
+
+/// Metrics in the Prometheus text format, grouped by name in the order they were added.
+#[derive(Default)]
+struct Metrics {
+    families: IndexMap<&'static str, (&'static str, &'static str, Vec<String>)>,
+}
+
+impl Metrics {
+    fn add(
+        &mut self,
+        name: &'static str,
+        kind: &'static str,
+        help: &'static str,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{key}=\"{}\"",
+                    value
+                        .replace('\\', "\\\\")
+                        .replace('"', "\\\"")
+                        .replace('\n', "\\n")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.families
+            .entry(name)
+            .or_insert((kind, help, Vec::new()))
+            .2
+            .push(format!("{name}{{{labels}}} {value}"));
+    }
+
+    fn render(&self) -> String {
+        let mut output = String::new();
+
+        for (name, (kind, help, samples)) in self.families.iter() {
+            writeln!(output, "# HELP {name} {help}").unwrap();
+            writeln!(output, "# TYPE {name} {kind}").unwrap();
+            for sample in samples {
+                writeln!(output, "{sample}").unwrap();
+            }
+        }
+
+        output
+    }
+}
+
+fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+
+            Some(if metadata.is_dir() {
+                directory_size(&entry.path())
+            } else {
+                metadata.len()
+            })
+        })
+        .sum()
+}
+
+type Cache<T> = Mutex<HashMap<PathBuf, (Instant, T)>>;
+
+fn cached<T: Clone>(cache: &Cache<T>, directory: &Path, ttl: Duration) -> Option<T> {
+    cache
+        .lock()
+        .unwrap()
+        .get(directory)
+        .filter(|(time, _)| time.elapsed() < ttl)
+        .map(|(_, value)| value.clone())
+}
+
+/// Collects the metrics of the current server, or of every registered server.
+pub struct Exporter {
+    all: bool,
+    system: Mutex<System>,
+    builds: Cache<Option<[u32; 2]>>,
+    worlds: Cache<Vec<(String, u64)>>,
+}
+
+impl Exporter {
+    pub fn new(all: bool) -> Self {
+        Self {
+            all,
+            system: Mutex::new(System::new()),
+            builds: Mutex::new(HashMap::new()),
+            worlds: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn servers(&self) -> Vec<(String, PathBuf)> {
+        let registry = Registry::load();
+
+        if self.all {
+            return registry
+                .servers
+                .into_iter()
+                .map(|server| (server.name, PathBuf::from(server.path)))
+                .collect();
+        }
+
+        let directory = std::env::current_dir().unwrap_or_default();
+
+        vec![(registry::name(&directory), directory)]
+    }
+
+    pub async fn render(&self) -> String {
+        let mut metrics = Metrics::default();
+
+        for (name, directory) in self.servers() {
+            self.collect(&mut metrics, &name, &directory).await;
+        }
+
+        metrics.render()
+    }
+
+    async fn collect(&self, metrics: &mut Metrics, name: &str, directory: &Path) {
+        let Some(config) = Config::new_optional(directory.join(".mcvcli.json").to_str().unwrap())
+        else {
+            return;
+        };
+
+        let labels = [("server", name), ("profile", config.profile_name.as_str())];
+        let properties =
+            Properties::new(directory.join(properties::FILE).to_str().unwrap()).unwrap_or_default();
+
+        let running = detached::running(&config);
+        metrics.add(
+            "mcvcli_up",
+            "gauge",
+            "Whether the server is running.",
+            &labels,
+            running as u8 as f64,
+        );
+        metrics.add(
+            "mcvcli_ram_limit_bytes",
+            "gauge",
+            "The maximum heap size of the server (ramMB).",
+            &labels,
+            config.ram_mb as f64 * 1024.0 * 1024.0,
+        );
+
+        for (world, size) in self.worlds(directory, &properties).await {
+            metrics.add(
+                "mcvcli_world_size_bytes",
+                "gauge",
+                "The size of a world on disk.",
+                &[labels[0], labels[1], ("world", &world)],
+                size as f64,
+            );
+        }
+
+        if let Some([installed, latest]) = self.builds(directory, &config).await {
+            metrics.add(
+                "mcvcli_build_id",
+                "gauge",
+                "The mcjars id of the installed build.",
+                &labels,
+                installed as f64,
+            );
+            metrics.add(
+                "mcvcli_latest_build_id",
+                "gauge",
+                "The mcjars id of the latest build of the installed version.",
+                &labels,
+                latest as f64,
+            );
+        }
+
+        if !running {
+            return;
+        }
+
+        let Some(mut client) = Client::connect(config.identifier.as_deref().unwrap_or_default())
+            .await
+            .ok()
+        else {
+            return;
+        };
+        let Ok(state) = client.state().await else {
+            return;
+        };
+
+        metrics.add(
+            "mcvcli_ready",
+            "gauge",
+            "Whether the server has finished starting.",
+            &labels,
+            state.ready as u8 as f64,
+        );
+        metrics.add(
+            "mcvcli_restarts_total",
+            "counter",
+            "The restarts of the server since its supervisor started.",
+            &labels,
+            state.restarts as f64,
+        );
+        if let Some(startup_ms) = state.startup_ms {
+            metrics.add(
+                "mcvcli_startup_seconds",
+                "gauge",
+                "The time the server took to start.",
+                &labels,
+                startup_ms as f64 / 1000.0,
+            );
+        }
+
+        if let Some(pid) = state.pid.map(|pid| pid as usize).or(config.pid) {
+            let pid = sysinfo::Pid::from(pid);
+            let mut system = self.system.lock().unwrap();
+            system.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[pid]),
+                true,
+                ProcessRefreshKind::nothing().with_cpu().with_memory(),
+            );
+
+            if let Some(process) = system.process(pid) {
+                metrics.add(
+                    "mcvcli_cpu_usage_percent",
+                    "gauge",
+                    "The cpu usage of the server since the last scrape, 100 per core.",
+                    &labels,
+                    process.cpu_usage() as f64,
+                );
+                metrics.add(
+                    "mcvcli_memory_rss_bytes",
+                    "gauge",
+                    "The resident memory of the server process.",
+                    &labels,
+                    process.memory() as f64,
+                );
+                metrics.add(
+                    "mcvcli_uptime_seconds",
+                    "gauge",
+                    "The time since the server process was started.",
+                    &labels,
+                    process.run_time() as f64,
+                );
+            }
+        }
+
+        let (host, port) = properties.server_address();
+        let ping = tokio::task::spawn_blocking(move || {
+            let mut server = msp::Conf::create_with_port(&host, port);
+            server.socket_conf.read_time_out = Some(Duration::from_secs(2));
+            server.socket_conf.write_timeout = Some(Duration::from_secs(2));
+
+            server.get_server_status().ok()
+        });
+
+        if let Ok(Ok(Some(server))) = tokio::time::timeout(Duration::from_secs(5), ping).await {
+            metrics.add(
+                "mcvcli_players_online",
+                "gauge",
+                "The players online according to a status ping.",
+                &labels,
+                server.players.online as f64,
+            );
+            metrics.add(
+                "mcvcli_players_max",
+                "gauge",
+                "The player limit according to a status ping.",
+                &labels,
+                server.players.max as f64,
+            );
+        }
+
+        let performance = client.performance().await.unwrap_or_default();
+
+        if let Some(tps) = performance.tps {
+            for (window, tps) in ["1m", "5m", "15m"].into_iter().zip(tps) {
+                metrics.add(
+                    "mcvcli_tps",
+                    "gauge",
+                    "The ticks per second reported by the tps command (paper and forks).",
+                    &[labels[0], labels[1], ("window", window)],
+                    tps,
+                );
+            }
+        }
+        if let Some(mspt) = performance.mspt {
+            metrics.add(
+                "mcvcli_mspt",
+                "gauge",
+                "The average tick time of the last 5s reported by the mspt command (paper and forks).",
+                &labels,
+                mspt,
+            );
+        }
+    }
+
+    /// The sizes of the overworld, nether and end of `level-name`.
+    async fn worlds(&self, directory: &Path, properties: &Properties) -> Vec<(String, u64)> {
+        if let Some(worlds) = cached(&self.worlds, directory, WORLD_CACHE) {
+            return worlds;
+        }
+
+        let level = properties.get("level-name").unwrap_or("world").to_string();
+        let root = directory.to_path_buf();
+        let worlds = tokio::task::spawn_blocking(move || {
+            [
+                level.clone(),
+                format!("{level}_nether"),
+                format!("{level}_the_end"),
+            ]
+            .into_iter()
+            .filter(|world| root.join(world).is_dir())
+            .map(|world| {
+                let size = directory_size(&root.join(&world));
+                (world, size)
+            })
+            .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+
+        self.worlds
+            .lock()
+            .unwrap()
+            .insert(directory.to_path_buf(), (Instant::now(), worlds.clone()));
+
+        worlds
+    }
+
+    async fn builds(&self, directory: &Path, config: &Config) -> Option<[u32; 2]> {
+        if let Some(builds) = cached(&self.builds, directory, BUILD_CACHE) {
+            return builds;
+        }
+
+        let jar = directory.join(&config.jar_file);
+        let builds = if jar.is_file() {
+            api::mcjars::lookup(jar.to_str().unwrap())
+                .await
+                .ok()
+                .map(|([build, latest], _)| [build.id, latest.id])
+        } else {
+            None
+        };
+
+        self.builds
+            .lock()
+            .unwrap()
+            .insert(directory.to_path_buf(), (Instant::now(), builds));
+
+        builds
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     api::{self, Progress, modrinth::Version},
+    error::Error,
     jar,
     progress::Progress as ProgressBar,
 };
@@ -43,8 +44,15 @@ struct IndexJsonFileEnv {
     server: String,
 }
 
-pub async fn install(directory: &str, version: &Version) {
-    let file = version.files.iter().find(|file| file.primary).unwrap();
+pub async fn install(directory: &str, version: &Version) -> Result<(), Error> {
+    let Some(file) = version
+        .files
+        .iter()
+        .find(|file| file.primary)
+        .or(version.files.first())
+    else {
+        return Err(Error::not_found("modpack version has no files"));
+    };
 
     println!(
         " {} {} {}",
@@ -53,8 +61,8 @@ pub async fn install(directory: &str, version: &Version) {
         "...".bright_black()
     );
 
-    let mut res = reqwest::get(&file.url).await.unwrap();
-    let mut mrpack_file = File::create(Path::new(directory).join(&file.filename)).unwrap();
+    let mut res = reqwest::get(&file.url).await?;
+    let mut mrpack_file = File::create(Path::new(directory).join(&file.filename))?;
 
     let mut progress = Progress::new(file.size as usize);
     progress.spinner(|progress, spinner| {
@@ -74,12 +82,12 @@ pub async fn install(directory: &str, version: &Version) {
         )
     });
 
-    while let Some(chunk) = res.chunk().await.unwrap() {
-        mrpack_file.write_all(&chunk).unwrap();
+    while let Some(chunk) = res.chunk().await? {
+        mrpack_file.write_all(&chunk)?;
         progress.incr(chunk.len());
     }
 
-    mrpack_file.sync_all().unwrap();
+    mrpack_file.sync_all()?;
     progress.finish();
     println!();
 
@@ -91,15 +99,14 @@ pub async fn install(directory: &str, version: &Version) {
         "DONE".green().bold().italic()
     );
 
-    let mut archive =
-        ZipArchive::new(File::open(Path::new(directory).join(&file.filename)).unwrap()).unwrap();
-    let index: IndexJson =
-        serde_json::from_reader(archive.by_name("modrinth.index.json").unwrap()).unwrap();
+    let mut archive = ZipArchive::new(File::open(Path::new(directory).join(&file.filename))?)?;
+    let index: IndexJson = serde_json::from_reader(archive.by_name("modrinth.index.json")?)
+        .map_err(|err| Error::io(format!("invalid modrinth.index.json: {err}")))?;
 
     println!(" {}", "extracting overrides...".bright_black().italic());
 
     std::fs::remove_dir_all(Path::new(directory).join("overrides")).unwrap_or_default();
-    archive.extract(directory).unwrap();
+    archive.extract(directory)?;
 
     std::fs::remove_file(Path::new(directory).join("modrinth.index.json")).unwrap_or_default();
 
@@ -111,16 +118,16 @@ pub async fn install(directory: &str, version: &Version) {
 
             if new_path.exists() {
                 if new_path.is_dir() {
-                    std::fs::remove_dir_all(&new_path).unwrap();
+                    std::fs::remove_dir_all(&new_path)?;
                 } else {
-                    std::fs::remove_file(&new_path).unwrap();
+                    std::fs::remove_file(&new_path)?;
                 }
             }
 
-            std::fs::rename(&file_path, &new_path).unwrap();
+            std::fs::rename(&file_path, &new_path)?;
         }
 
-        std::fs::remove_dir_all(Path::new(directory).join("overrides")).unwrap();
+        std::fs::remove_dir_all(Path::new(directory).join("overrides"))?;
     }
 
     std::fs::remove_file(&file.filename).unwrap_or_default();
@@ -133,7 +140,7 @@ pub async fn install(directory: &str, version: &Version) {
 
     println!(" {}", "downloading files...".bright_black().italic());
 
-    let terminal_width = term_size::dimensions().unwrap().0 as usize;
+    let terminal_width = term_size::dimensions().map_or(80, |(width, _)| width);
     for files in index.files.chunks(10) {
         let progress = Arc::new(Mutex::new(ProgressBar::with_capacity(10)));
         let mut handles = Vec::new();
@@ -168,23 +175,27 @@ pub async fn install(directory: &str, version: &Version) {
                 let file_name = file_path.display().to_string();
 
                 if !file_path.parent().unwrap().exists() {
-                    std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+                    std::fs::create_dir_all(file_path.parent().unwrap())?;
                 }
 
-                let mut res = reqwest::get(download).await.unwrap();
-                let mut mod_file = File::create(&file_name).unwrap();
+                let mut res = reqwest::get(download).await?;
+                let mut mod_file = File::create(&file_name)?;
 
-                while let Some(chunk) = res.chunk().await.unwrap() {
-                    mod_file.write_all(&chunk).unwrap();
+                while let Some(chunk) = res.chunk().await? {
+                    mod_file.write_all(&chunk)?;
                     progress.lock().await.inc_and_draw(&bar, chunk.len());
                 }
 
-                mod_file.sync_all().unwrap();
+                mod_file.sync_all()?;
+
+                Ok::<_, Error>(())
             }));
         }
 
         for handle in handles {
-            handle.await.unwrap();
+            handle
+                .await
+                .map_err(|err| Error::other(err.to_string()))??;
         }
     }
 
@@ -196,12 +207,16 @@ pub async fn install(directory: &str, version: &Version) {
 
     let minecraft = index.dependencies.minecraft;
     if let Some(fabric_loader) = index.dependencies.fabric_loader {
-        let builds = api::mcjars::builds("FABRIC", &minecraft).await.unwrap();
+        let builds = api::mcjars::builds("FABRIC", &minecraft).await?;
 
-        let build = builds
+        let Some(build) = builds
             .iter()
             .find(|build| build.project_version_id.as_ref() == Some(&fabric_loader))
-            .unwrap();
+        else {
+            return Err(Error::not_found(format!(
+                "fabric loader {fabric_loader} was not found"
+            )));
+        };
 
         println!(
             " {} {} {}",
@@ -210,7 +225,7 @@ pub async fn install(directory: &str, version: &Version) {
             "...".bright_black().italic()
         );
 
-        jar::install(build, directory, 2).await.unwrap();
+        jar::install(build, directory, 2).await?;
 
         println!(
             " {} {} {} {}",
@@ -220,12 +235,16 @@ pub async fn install(directory: &str, version: &Version) {
             "DONE".green().bold().italic()
         );
     } else if let Some(quilt_loader) = index.dependencies.quilt_loader {
-        let builds = api::mcjars::builds("QUILT", &minecraft).await.unwrap();
+        let builds = api::mcjars::builds("QUILT", &minecraft).await?;
 
-        let build = builds
+        let Some(build) = builds
             .iter()
             .find(|build| build.project_version_id.as_ref() == Some(&quilt_loader))
-            .unwrap();
+        else {
+            return Err(Error::not_found(format!(
+                "quilt loader {quilt_loader} was not found"
+            )));
+        };
 
         println!(
             " {} {} {}",
@@ -234,7 +253,7 @@ pub async fn install(directory: &str, version: &Version) {
             "...".bright_black().italic()
         );
 
-        jar::install(build, directory, 2).await.unwrap();
+        jar::install(build, directory, 2).await?;
 
         println!(
             " {} {} {} {}",
@@ -244,12 +263,16 @@ pub async fn install(directory: &str, version: &Version) {
             "DONE".green().bold().italic()
         );
     } else if let Some(forge) = index.dependencies.forge {
-        let builds = api::mcjars::builds("FORGE", &minecraft).await.unwrap();
+        let builds = api::mcjars::builds("FORGE", &minecraft).await?;
 
-        let build = builds
+        let Some(build) = builds
             .iter()
             .find(|build| build.project_version_id.as_ref() == Some(&forge))
-            .unwrap();
+        else {
+            return Err(Error::not_found(format!(
+                "forge loader {forge} was not found"
+            )));
+        };
 
         println!(
             " {} {} {}",
@@ -258,7 +281,7 @@ pub async fn install(directory: &str, version: &Version) {
             "...".bright_black().italic()
         );
 
-        jar::install(build, directory, 2).await.unwrap();
+        jar::install(build, directory, 2).await?;
 
         println!(
             " {} {} {} {}",
@@ -268,12 +291,16 @@ pub async fn install(directory: &str, version: &Version) {
             "DONE".green().bold().italic()
         );
     } else if let Some(neoforge) = index.dependencies.neoforge {
-        let builds = api::mcjars::builds("NEOFORGE", &minecraft).await.unwrap();
+        let builds = api::mcjars::builds("NEOFORGE", &minecraft).await?;
 
-        let build = builds
+        let Some(build) = builds
             .iter()
             .find(|build| build.project_version_id.as_ref() == Some(&neoforge))
-            .unwrap();
+        else {
+            return Err(Error::not_found(format!(
+                "neoforge loader {neoforge} was not found"
+            )));
+        };
 
         println!(
             " {} {} {}",
@@ -282,7 +309,7 @@ pub async fn install(directory: &str, version: &Version) {
             "...".bright_black().italic()
         );
 
-        jar::install(build, directory, 2).await.unwrap();
+        jar::install(build, directory, 2).await?;
 
         println!(
             " {} {} {} {}",
@@ -292,4 +319,6 @@ pub async fn install(directory: &str, version: &Version) {
             "DONE".green().bold().italic()
         );
     }
+
+    Ok(())
 }
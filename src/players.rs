@@ -0,0 +1,371 @@
+use crate::logs;
+
+use chrono::{Local, NaiveDateTime};
+use indexmap::IndexMap;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fs::{File, OpenOptions},
+    os::fd::AsRawFd,
+    path::Path,
+    sync::LazyLock,
+};
+
+pub const FILE: &str = ".mcvcli/players.json";
+/// Held by the supervisor while it runs, other processes only read the store meanwhile.
+const LOCK: &str = ".mcvcli/players.lock";
+
+/// The closed sessions kept, the oldest ones are dropped beyond that.
+const MAX_SESSIONS: usize = 10_000;
+
+/// Joins and deaths within this many seconds of a recorded one are considered the same,
+/// the console stream and the log files see the same lines.
+const TOLERANCE: i64 = 2;
+
+static JOINED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\w{1,16})(?: \(formerly known as \w{1,16}\))? joined the game").unwrap()
+});
+static LEFT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\w{1,16}) left the game").unwrap());
+static CHAT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:\[Not Secure\] )?<(\w{1,16})> (.*)$").unwrap());
+static DEATH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(\w{1,16}) (was |drowned|died|blew up|burned|fell |hit the ground|starved|suffocated|froze|went (up in flames|off with a bang)|tried to swim in lava|experienced kinetic energy|withered away|discovered the floor was lava|walked into|didn't want to live|left the confines)",
+    )
+    .unwrap()
+});
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Activity {
+    Join(String),
+    Leave(String),
+    Chat(String),
+    Death(String, String),
+}
+
+impl Activity {
+    /// Parses the message of a log entry, deaths are checked against the online players
+    /// by `Store::record`.
+    pub fn parse(message: &str) -> Option<Self> {
+        if let Some(captures) = JOINED.captures(message) {
+            return Some(Self::Join(captures[1].to_string()));
+        }
+        if let Some(captures) = LEFT.captures(message) {
+            return Some(Self::Leave(captures[1].to_string()));
+        }
+        if let Some(captures) = CHAT.captures(message) {
+            return Some(Self::Chat(captures[1].to_string()));
+        }
+        if let Some(captures) = DEATH.captures(message) {
+            return Some(Self::Death(captures[1].to_string(), message.to_string()));
+        }
+
+        None
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Join(name) | Self::Leave(name) | Self::Chat(name) | Self::Death(name, _) => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Death {
+    pub time: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub name: String,
+    pub joined: i64,
+    pub left: Option<i64>,
+    pub last_seen: i64,
+    pub chats: u32,
+    pub deaths: Vec<Death>,
+}
+
+impl Session {
+    #[inline]
+    pub fn duration(&self, now: i64) -> i64 {
+        self.left.unwrap_or(now) - self.joined
+    }
+}
+
+/// The player sessions of the server, stored in `.mcvcli/players.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Store {
+    pub sessions: Vec<Session>,
+    /// The bytes of each log file that were imported already.
+    #[serde(default)]
+    imported: IndexMap<String, usize>,
+}
+
+impl Store {
+    pub fn load() -> Self {
+        match File::open(FILE) {
+            Ok(file) => serde_json::from_reader(file).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        if let Ok(content) = serde_json::to_vec(self) {
+            write(&content);
+        }
+    }
+
+    fn open(&mut self, name: &str) -> Option<&mut Session> {
+        self.sessions
+            .iter_mut()
+            .rev()
+            .find(|session| session.left.is_none() && session.name == name)
+    }
+
+    #[inline]
+    pub fn online(&self) -> impl Iterator<Item = &Session> {
+        self.sessions
+            .iter()
+            .filter(|session| session.left.is_none())
+    }
+
+    /// Records an activity, returns whether it changed the store.
+    pub fn record(&mut self, time: i64, activity: &Activity) -> bool {
+        match activity {
+            Activity::Join(name) => {
+                if self.sessions.iter().any(|session| {
+                    session.name == *name && (session.joined - time).abs() <= TOLERANCE
+                }) {
+                    return false;
+                }
+
+                // the leave of the previous session was missed
+                if let Some(session) = self.open(name) {
+                    session.left = Some(session.last_seen);
+                }
+
+                self.sessions.push(Session {
+                    name: name.clone(),
+                    joined: time,
+                    left: None,
+                    last_seen: time,
+                    chats: 0,
+                    deaths: Vec::new(),
+                });
+
+                if self.sessions.len() > MAX_SESSIONS
+                    && let Some(oldest) = self
+                        .sessions
+                        .iter()
+                        .position(|session| session.left.is_some())
+                {
+                    self.sessions.remove(oldest);
+                }
+            }
+            Activity::Leave(name) => {
+                let Some(session) = self.open(name) else {
+                    return false;
+                };
+
+                session.left = Some(time.max(session.joined));
+                session.last_seen = time.max(session.last_seen);
+            }
+            Activity::Chat(name) => {
+                let Some(session) = self.open(name) else {
+                    return false;
+                };
+
+                session.chats += 1;
+                session.last_seen = time.max(session.last_seen);
+            }
+            Activity::Death(name, message) => {
+                let Some(session) = self.open(name) else {
+                    return false;
+                };
+
+                if session
+                    .deaths
+                    .iter()
+                    .any(|death| (death.time - time).abs() <= TOLERANCE)
+                {
+                    return false;
+                }
+
+                session.deaths.push(Death {
+                    time,
+                    message: message.clone(),
+                });
+                session.last_seen = time.max(session.last_seen);
+            }
+        }
+
+        true
+    }
+
+    /// Ends the open sessions at `time`, or when the player was last seen.
+    pub fn close(&mut self, time: Option<i64>) -> bool {
+        let mut changed = false;
+
+        for session in self.sessions.iter_mut() {
+            if session.left.is_none() {
+                session.left = Some(time.unwrap_or(session.last_seen).max(session.joined));
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    fn import_content(&mut self, content: &str, path: &Path) {
+        // players whose session was already recorded from the console, their
+        // chats and deaths were counted already
+        let mut recorded = HashSet::new();
+
+        for entry in logs::parse(content, logs::date(path)) {
+            let (Some(time), Some(activity)) = (entry.time, Activity::parse(&entry.message)) else {
+                continue;
+            };
+            let time = timestamp(time);
+
+            match &activity {
+                Activity::Join(name) => {
+                    if self.record(time, &activity) {
+                        recorded.remove(name);
+                    } else {
+                        recorded.insert(name.clone());
+                    }
+                }
+                Activity::Leave(name) if recorded.remove(name) => {
+                    self.record(time, &activity);
+                }
+                _ if recorded.contains(activity.name()) => {}
+                _ => {
+                    self.record(time, &activity);
+                }
+            }
+        }
+    }
+
+    /// Imports the rotated logs and the new part of `logs/latest.log`, returns whether
+    /// anything was imported.
+    pub fn import(&mut self) -> bool {
+        let mut changed = false;
+
+        let mut files = logs::archives()
+            .into_iter()
+            .map(|(_, path)| path)
+            .collect::<Vec<_>>();
+        files.push(logs::LATEST.into());
+
+        for path in files {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let imported = self.imported.get(&name).copied().unwrap_or(0);
+            if imported > 0 && name != "latest.log" {
+                continue;
+            }
+
+            let Ok(content) = logs::read(&path) else {
+                continue;
+            };
+            if content.len() == imported {
+                continue;
+            }
+
+            // latest.log was rotated since the last import
+            let new = content
+                .get(imported..)
+                .filter(|_| content.len() > imported)
+                .unwrap_or(&content);
+            self.import_content(new, &path);
+            self.imported.insert(name, content.len());
+            changed = true;
+        }
+
+        if changed {
+            self.sessions.sort_by_key(|session| session.joined);
+        }
+
+        changed
+    }
+}
+
+#[inline]
+pub fn timestamp(time: NaiveDateTime) -> i64 {
+    time.and_local_timezone(Local)
+        .earliest()
+        .map(|time| time.timestamp())
+        .unwrap_or_else(|| time.and_utc().timestamp())
+}
+
+/// Parses a line of the console.
+pub fn activity(line: &str) -> Option<Activity> {
+    let entry = logs::parse(line, Local::now().date_naive()).pop()?;
+
+    Activity::parse(&entry.message)
+}
+
+/// Locks the store against other mcvcli processes, `None` if another one holds it
+/// (or it cannot be created) and `blocking` is not set.
+pub fn lock(blocking: bool) -> Option<File> {
+    std::fs::create_dir_all(".mcvcli").ok()?;
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(LOCK)
+        .ok()?;
+
+    let operation = if blocking {
+        libc::LOCK_EX
+    } else {
+        libc::LOCK_EX | libc::LOCK_NB
+    };
+
+    (unsafe { libc::flock(file.as_raw_fd(), operation) } == 0).then_some(file)
+}
+
+/// Replaces the store, written to a temporary file first so readers never see half of it.
+pub fn write(content: &[u8]) {
+    let temporary = format!("{FILE}.tmp");
+
+    std::fs::create_dir_all(".mcvcli").unwrap_or_default();
+    if std::fs::write(&temporary, content).is_ok() {
+        std::fs::rename(&temporary, FILE).unwrap_or_default();
+    }
+}
+
+/// Loads the store with the new log lines imported, they are only saved while no
+/// supervisor holds the store.
+pub fn load() -> Store {
+    let lock = lock(false);
+
+    let mut store = Store::load();
+    if store.import() && lock.is_some() {
+        store.save();
+    }
+
+    store
+}
+
+pub fn format_time(time: i64) -> String {
+    chrono::DateTime::from_timestamp(time, 0)
+        .map(|time| {
+            time.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+pub fn format_duration(seconds: i64) -> String {
+    format!(
+        "{}h {}m {}s",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
@@ -0,0 +1,159 @@
+use crate::config::Config;
+use crate::properties::{self, Properties};
+
+use human_bytes::human_bytes;
+use std::{
+    net::{TcpListener, UdpSocket},
+    path::Path,
+};
+use sysinfo::{Disks, System};
+
+/// The free disk space below which the server is not started, worlds and logs need room to grow.
+const MIN_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    Tcp,
+    Udp,
+}
+
+/// Checks that the ports of the server are free and that there is enough memory and disk
+/// space, returns the problems found.
+pub fn check(config: &Config) -> Vec<String> {
+    let properties = Properties::new(properties::FILE).unwrap_or_default();
+    let host = match properties.get("server-ip") {
+        Some(ip) if !ip.is_empty() => ip.to_string(),
+        _ => "0.0.0.0".to_string(),
+    };
+
+    let server_port = properties.get_parsed::<u16>("server-port").unwrap_or(25565);
+    let mut ports = vec![("server-port", server_port, Protocol::Tcp)];
+    if properties.get("enable-query") == Some("true") {
+        ports.push((
+            "query.port",
+            properties.get_parsed("query.port").unwrap_or(server_port),
+            Protocol::Udp,
+        ));
+    }
+    if properties.get("enable-rcon") == Some("true") {
+        ports.push((
+            "rcon.port",
+            properties.get_parsed("rcon.port").unwrap_or(25575),
+            Protocol::Tcp,
+        ));
+    }
+
+    let mut problems = Vec::new();
+
+    for (key, port, protocol) in ports {
+        let bound = match protocol {
+            Protocol::Tcp => TcpListener::bind((host.as_str(), port)).map(|_| ()),
+            Protocol::Udp => UdpSocket::bind((host.as_str(), port)).map(|_| ()),
+        };
+
+        match bound {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::AddrInUse => {
+                problems.push(format!(
+                    "port {port} ({key}) is already in use by {}",
+                    owner(port, protocol).unwrap_or("another process".to_string())
+                ));
+            }
+            Err(err) => problems.push(format!("port {port} ({key}) cannot be bound: {err}")),
+        }
+    }
+
+    let mut system = System::new();
+    system.refresh_memory();
+
+    let ram = config.ram_mb as u64 * 1024 * 1024;
+    if system.available_memory() < ram {
+        problems.push(format!(
+            "only {} of memory are available, the server is allowed to use {} (ramMB)",
+            human_bytes(system.available_memory() as f64),
+            human_bytes(ram as f64)
+        ));
+    }
+
+    let directory = std::env::current_dir().unwrap_or_default();
+    let disks = Disks::new_with_refreshed_list();
+    if let Some(disk) = disks
+        .list()
+        .iter()
+        .filter(|disk| directory.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        && disk.available_space() < MIN_DISK_SPACE
+    {
+        problems.push(format!(
+            "only {} of disk space are left on {}",
+            human_bytes(disk.available_space() as f64),
+            disk.mount_point().display()
+        ));
+    }
+
+    problems
+}
+
+/// Finds the process listening on `port` through `/proc/net` and the socket inodes
+/// of every process, e.g. `java (pid 1234) in /srv/lobby`.
+fn owner(port: u16, protocol: Protocol) -> Option<String> {
+    let files: &[&str] = match protocol {
+        Protocol::Tcp => &["/proc/net/tcp", "/proc/net/tcp6"],
+        Protocol::Udp => &["/proc/net/udp", "/proc/net/udp6"],
+    };
+
+    let inodes = files
+        .iter()
+        .filter_map(|file| std::fs::read_to_string(file).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .skip(1)
+                .filter_map(|line| {
+                    let fields = line.split_whitespace().collect::<Vec<_>>();
+                    let local_port =
+                        u16::from_str_radix(fields.get(1)?.rsplit_once(':')?.1, 16).ok()?;
+
+                    // 0A is LISTEN, udp sockets have no listen state
+                    if local_port != port || (protocol == Protocol::Tcp && fields.get(3)? != &"0A")
+                    {
+                        return None;
+                    }
+
+                    Some(format!("socket:[{}]", fields.get(9)?))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    if inodes.is_empty() {
+        return None;
+    }
+
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let holds = fds.flatten().any(|fd| {
+            std::fs::read_link(fd.path())
+                .is_ok_and(|link| inodes.iter().any(|inode| Path::new(inode) == link))
+        });
+        if !holds {
+            continue;
+        }
+
+        let name = std::fs::read_to_string(entry.path().join("comm")).unwrap_or_default();
+        let mut owner = format!("{} (pid {pid})", name.trim());
+        if let Ok(cwd) = std::fs::read_link(entry.path().join("cwd")) {
+            owner.push_str(&format!(" in {}", cwd.display()));
+        }
+
+        return Some(owner);
+    }
+
+    None
+}
@@ -0,0 +1,59 @@
+use std::{collections::HashMap, str::FromStr};
+
+pub const FILE: &str = "server.properties";
+
+#[derive(Debug, Default)]
+pub struct Properties {
+    values: HashMap<String, String>,
+}
+
+impl Properties {
+    pub fn new(path: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+
+        Some(Self::parse(&content))
+    }
+
+    /// Parses the `key=value` lines of a java properties file, skipping comments.
+    pub fn parse(content: &str) -> Self {
+        let mut values = HashMap::new();
+
+        for line in content.lines() {
+            let line = line.trim_start();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+                continue;
+            }
+
+            let (key, value) = match line.split_once(['=', ':']) {
+                Some((key, value)) => (key.trim(), value.trim_start()),
+                None => (line.trim(), ""),
+            };
+
+            values.insert(
+                key.replace("\\:", ":").replace("\\=", "="),
+                value.replace("\\:", ":").replace("\\=", "="),
+            );
+        }
+
+        Self { values }
+    }
+
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|value| value.as_str())
+    }
+
+    pub fn get_parsed<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key)?.parse().ok()
+    }
+
+    /// The address the server listens on, `server-ip` falls back to localhost.
+    pub fn server_address(&self) -> (String, u16) {
+        let host = match self.get("server-ip") {
+            Some(ip) if !ip.is_empty() => ip.to_string(),
+            _ => "127.0.0.1".to_string(),
+        };
+
+        (host, self.get_parsed("server-port").unwrap_or(25565))
+    }
+}
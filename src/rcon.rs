@@ -0,0 +1,176 @@
+use crate::config::RconConfig;
+use crate::properties::{self, Properties};
+
+use std::time::Duration;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+const TYPE_RESPONSE: i32 = 0;
+const TYPE_COMMAND: i32 = 2;
+const TYPE_AUTH: i32 = 3;
+
+const MAX_COMMAND_SIZE: usize = 1446;
+const MAX_PACKET_SIZE: usize = 4096 + 10;
+
+#[derive(Debug, Clone)]
+pub struct Address {
+    pub host: String,
+    pub port: u16,
+    pub password: String,
+}
+
+impl Address {
+    /// Resolves the rcon address from the config, falling back to `server.properties`.
+    pub fn resolve(config: &RconConfig) -> Result<Self, String> {
+        let properties = Properties::new(properties::FILE).unwrap_or_default();
+
+        let password = match config
+            .password
+            .as_deref()
+            .or(properties.get("rcon.password"))
+        {
+            Some(password) if !password.is_empty() => password.to_string(),
+            _ => return Err("no rcon password set in server.properties".to_string()),
+        };
+
+        if config.port.is_none() && properties.get_parsed::<bool>("enable-rcon") != Some(true) {
+            return Err(
+                "rcon is not enabled, set enable-rcon=true in server.properties".to_string(),
+            );
+        }
+
+        Ok(Self {
+            host: config.host.clone(),
+            port: config
+                .port
+                .or(properties.get_parsed("rcon.port"))
+                .unwrap_or(25575),
+            password,
+        })
+    }
+}
+
+pub struct Rcon {
+    stream: TcpStream,
+    id: i32,
+}
+
+impl Rcon {
+    pub async fn connect(address: &Address) -> std::io::Result<Self> {
+        let stream = tokio::time::timeout(
+            Duration::from_secs(5),
+            TcpStream::connect((address.host.as_str(), address.port)),
+        )
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "connection timed out"))??;
+
+        let mut rcon = Self { stream, id: 0 };
+
+        let id = rcon.next_id();
+        rcon.write(id, TYPE_AUTH, &address.password).await?;
+
+        loop {
+            let (response_id, kind, _) = rcon.read().await?;
+
+            // servers may send an empty response value before the auth response
+            if kind == TYPE_RESPONSE {
+                continue;
+            }
+
+            if response_id == -1 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "invalid rcon password",
+                ));
+            }
+
+            return Ok(rcon);
+        }
+    }
+
+    /// Runs a command and returns its output. A second packet is sent right after the
+    /// command, its reply marks the end of a response that was split over multiple packets.
+    pub async fn command(&mut self, command: &str) -> std::io::Result<String> {
+        if command.len() > MAX_COMMAND_SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("command exceeds {MAX_COMMAND_SIZE} bytes"),
+            ));
+        }
+
+        let id = self.next_id();
+        let marker = self.next_id();
+
+        self.write(id, TYPE_COMMAND, command).await?;
+        self.write(marker, TYPE_RESPONSE, "").await?;
+
+        let mut output = String::new();
+        loop {
+            let (response_id, _, body) = self.read().await?;
+
+            if response_id == marker {
+                break;
+            } else if response_id == id {
+                output.push_str(&body);
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn next_id(&mut self) -> i32 {
+        self.id = self.id.wrapping_add(1).max(1);
+
+        self.id
+    }
+
+    async fn write(&mut self, id: i32, kind: i32, body: &str) -> std::io::Result<()> {
+        let mut packet = Vec::with_capacity(body.len() + 14);
+        packet.extend_from_slice(&(body.len() as i32 + 10).to_le_bytes());
+        packet.extend_from_slice(&id.to_le_bytes());
+        packet.extend_from_slice(&kind.to_le_bytes());
+        packet.extend_from_slice(body.as_bytes());
+        packet.extend_from_slice(&[0, 0]);
+
+        self.stream.write_all(&packet).await
+    }
+
+    async fn read(&mut self) -> std::io::Result<(i32, i32, String)> {
+        let length = self.stream.read_i32_le().await? as usize;
+        if !(10..=MAX_PACKET_SIZE).contains(&length) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid rcon packet length {length}"),
+            ));
+        }
+
+        let id = self.stream.read_i32_le().await?;
+        let kind = self.stream.read_i32_le().await?;
+
+        let mut body = vec![0; length - 8];
+        self.stream.read_exact(&mut body).await?;
+        body.truncate(length - 10);
+
+        Ok((id, kind, String::from_utf8_lossy(&body).to_string()))
+    }
+}
+
+/// Sends the stop command, the server may close the connection before it replies.
+pub async fn stop(address: &Address, command: &str) -> std::io::Result<()> {
+    let mut rcon = Rcon::connect(address).await?;
+
+    match tokio::time::timeout(Duration::from_secs(5), rcon.command(command)).await {
+        Ok(Ok(_)) | Err(_) => Ok(()),
+        Ok(Err(err))
+            if matches!(
+                err.kind(),
+                std::io::ErrorKind::UnexpectedEof | std::io::ErrorKind::ConnectionReset
+            ) =>
+        {
+            Ok(())
+        }
+        Ok(Err(err)) => Err(err),
+    }
+}
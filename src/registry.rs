@@ -0,0 +1,105 @@
+use dirs::home_dir;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+pub static FILE: LazyLock<PathBuf> =
+    LazyLock::new(|| home_dir().unwrap().join(".mcvcli").join("servers.json"));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Server {
+    pub name: String,
+    pub path: String,
+}
+
+/// The servers of this machine, stored in `~/.mcvcli/servers.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Registry {
+    pub servers: Vec<Server>,
+}
+
+impl Registry {
+    pub fn load() -> Self {
+        std::fs::read_to_string(&*FILE)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        std::fs::create_dir_all(FILE.parent().unwrap())?;
+        std::fs::write(&*FILE, serde_json::to_string_pretty(self)?)
+    }
+
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Server> {
+        self.servers.iter().find(|server| server.name == name)
+    }
+
+    pub fn find(&self, directory: &Path) -> Option<&Server> {
+        let directory = absolute(directory);
+
+        self.servers
+            .iter()
+            .find(|server| Path::new(&server.path) == directory)
+    }
+
+    /// The directory name of `directory`, suffixed with a number if it is already taken.
+    pub fn free_name(&self, directory: &Path) -> String {
+        let base = absolute(directory)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or("server".to_string());
+
+        let mut name = base.clone();
+        let mut i = 2;
+        while self.get(&name).is_some() {
+            name = format!("{base}-{i}");
+            i += 1;
+        }
+
+        name
+    }
+
+    pub fn add(&mut self, name: &str, directory: &Path) {
+        self.servers.push(Server {
+            name: name.to_string(),
+            path: absolute(directory).to_string_lossy().to_string(),
+        });
+        self.servers.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+fn absolute(directory: &Path) -> PathBuf {
+    directory
+        .canonicalize()
+        .or_else(|_| std::path::absolute(directory))
+        .unwrap_or(directory.to_path_buf())
+}
+
+/// Registers the server in `directory` under its directory name unless it already is,
+/// returns the name if it was added.
+pub fn register(directory: &Path) -> Option<String> {
+    let mut registry = Registry::load();
+    if registry.find(directory).is_some() {
+        return None;
+    }
+
+    let name = registry.free_name(directory);
+    registry.add(&name, directory);
+    registry.save().ok()?;
+
+    Some(name)
+}
+
+/// The registered name of the server in `directory`, or the name it would be registered as.
+pub fn name(directory: &Path) -> String {
+    let registry = Registry::load();
+
+    registry
+        .find(directory)
+        .map(|server| server.name.clone())
+        .unwrap_or_else(|| registry.free_name(directory))
+}
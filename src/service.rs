@@ -0,0 +1,182 @@
+use crate::config::{Config, RestartPolicy};
+
+use std::path::{Path, PathBuf};
+
+/// The default unit name for a server directory, `mcvcli-{directory name}`.
+pub fn name(directory: &Path) -> String {
+    let name = directory
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+
+    if name.trim_matches('-').is_empty() {
+        "mcvcli-server".to_string()
+    } else {
+        format!("mcvcli-{}", name.trim_matches('-'))
+    }
+}
+
+/// The path a unit gets installed to, `~/.config/systemd/user` for user units.
+pub fn path(name: &str, system: bool) -> PathBuf {
+    let directory = if system {
+        PathBuf::from("/etc/systemd/system")
+    } else {
+        dirs::config_dir()
+            .unwrap_or_else(|| dirs::home_dir().unwrap().join(".config"))
+            .join("systemd/user")
+    };
+
+    directory.join(format!("{name}.service"))
+}
+
+/// Runs `systemctl` for user or system units, returning its trimmed stdout.
+pub fn systemctl(system: bool, args: &[&str]) -> Result<String, String> {
+    let mut command = std::process::Command::new("systemctl");
+    if !system {
+        command.arg("--user");
+    }
+
+    let output = command
+        .args(args)
+        .output()
+        .map_err(|err| format!("unable to run systemctl: {err}"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(if stderr.is_empty() { stdout } else { stderr })
+    }
+}
+
+/// Quotes a command line argument for `ExecStart=` and friends.
+fn quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && !arg
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';' | '$' | '%'))
+    {
+        return arg.to_string();
+    }
+
+    format!(
+        "\"{}\"",
+        arg.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "$$")
+            .replace('%', "%%")
+    )
+}
+
+/// Renders the unit running the server in `directory` in the foreground.
+///
+/// `mcvcli start` already restarts the server according to the restart policy, systemd
+/// only takes over once it gives up (or when mcvcli itself dies). `user` runs the server
+/// of a system unit as that user.
+pub fn render(
+    config: &Config,
+    directory: &Path,
+    executable: &Path,
+    timeout: u64,
+    system: bool,
+    user: Option<&str>,
+) -> String {
+    let executable = quote(&executable.to_string_lossy());
+    let mut lines = vec![
+        "[Unit]".to_string(),
+        format!("Description=Minecraft server in {}", directory.display()),
+        "After=network-online.target".to_string(),
+        "Wants=network-online.target".to_string(),
+        format!("StartLimitIntervalSec={}", config.restart.window_seconds),
+        format!("StartLimitBurst={}", config.restart.max_retries.max(1)),
+        String::new(),
+        "[Service]".to_string(),
+        "Type=simple".to_string(),
+    ];
+
+    if system && let Some(user) = user {
+        lines.push(format!("User={user}"));
+    }
+
+    lines.push(format!("WorkingDirectory={}", directory.display()));
+    for key in ["JAVA_HOME", "PATH"] {
+        if let Ok(value) = std::env::var(key) {
+            lines.push(format!(
+                "Environment=\"{key}={}\"",
+                value
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('%', "%%")
+            ));
+        }
+    }
+
+    // mcvcli skips its own cgroup inside of units, systemd enforces these instead
+    lines.push("Environment=MCVCLI_SERVICE=1".to_string());
+    let limits = &config.limits;
+    if let Some(memory_max_mb) = limits.memory_max_mb {
+        lines.push(format!("MemoryMax={memory_max_mb}M"));
+    }
+    if let Some(cpu_max_percent) = limits.cpu_max_percent {
+        lines.push(format!("CPUQuota={cpu_max_percent}%"));
+    }
+    if let Some(nice) = limits.nice {
+        lines.push(format!("Nice={nice}"));
+    }
+    if !limits.cpu_affinity.is_empty() {
+        lines.push(format!(
+            "CPUAffinity={}",
+            limits
+                .cpu_affinity
+                .iter()
+                .map(|cpu| cpu.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+    }
+    if let Some(nofile) = limits.nofile {
+        lines.push(format!("LimitNOFILE={nofile}"));
+    }
+
+    lines.extend([
+        format!("ExecStart={executable} start --timeout {timeout}"),
+        format!("ExecStop={executable} stop --timeout {timeout}"),
+        "StandardInput=null".to_string(),
+        // SIGINT makes mcvcli stop the server gracefully, stragglers get SIGKILL
+        "KillMode=mixed".to_string(),
+        "KillSignal=SIGINT".to_string(),
+        format!("TimeoutStopSec={}", timeout + 10),
+        format!(
+            "Restart={}",
+            match config.restart.policy {
+                RestartPolicy::Never => "no",
+                RestartPolicy::OnFailure => "on-failure",
+                RestartPolicy::Always => "always",
+            }
+        ),
+        format!("RestartSec={}", config.restart.backoff_seconds),
+        String::new(),
+        "[Install]".to_string(),
+        format!(
+            "WantedBy={}",
+            if system {
+                "multi-user.target"
+            } else {
+                "default.target"
+            }
+        ),
+    ]);
+
+    lines.join("\n") + "\n"
+}
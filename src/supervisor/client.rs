@@ -0,0 +1,121 @@
+use super::performance::Performance;
+use super::protocol::{self, Event, Request, Response, State};
+
+use regex::Regex;
+use std::time::Duration;
+use tokio::net::{
+    UnixStream,
+    unix::{OwnedReadHalf, OwnedWriteHalf},
+};
+
+#[derive(Debug, Default)]
+pub struct Capture {
+    pub lines: Vec<String>,
+    pub matched: bool,
+    pub exited: bool,
+}
+
+pub struct Client {
+    pub reader: OwnedReadHalf,
+    pub writer: OwnedWriteHalf,
+}
+
+impl Client {
+    pub async fn connect(identifier: &str) -> std::io::Result<Self> {
+        let stream = UnixStream::connect(super::socket_path(identifier)).await?;
+        let (reader, writer) = stream.into_split();
+
+        Ok(Self { reader, writer })
+    }
+
+    #[inline]
+    pub async fn send(&mut self, request: &Request) -> std::io::Result<()> {
+        protocol::write(&mut self.writer, request).await
+    }
+
+    #[inline]
+    pub async fn recv(&mut self) -> std::io::Result<Option<Response>> {
+        protocol::read(&mut self.reader).await
+    }
+
+    pub async fn performance(&mut self) -> std::io::Result<Performance> {
+        self.send(&Request::Performance).await?;
+
+        loop {
+            match self.recv().await? {
+                Some(Response::Performance { performance }) => return Ok(performance),
+                Some(Response::Error { message }) => {
+                    return Err(std::io::Error::other(message));
+                }
+                Some(_) => continue,
+                None => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            }
+        }
+    }
+
+    pub async fn state(&mut self) -> std::io::Result<State> {
+        self.send(&Request::State).await?;
+
+        loop {
+            match self.recv().await? {
+                Some(Response::State { state }) => return Ok(state),
+                Some(Response::Error { message }) => {
+                    return Err(std::io::Error::other(message));
+                }
+                Some(_) => continue,
+                None => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            }
+        }
+    }
+
+    /// Runs a command and collects the console lines that follow it until `timeout`
+    /// passed or a line matched `until`.
+    pub async fn capture(
+        &mut self,
+        command: &str,
+        timeout: Duration,
+        until: Option<&Regex>,
+    ) -> std::io::Result<Capture> {
+        self.send(&Request::Subscribe { tail: 0 }).await?;
+        self.send(&Request::Command {
+            command: command.to_string(),
+        })
+        .await?;
+
+        let mut capture = Capture::default();
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            let response = match tokio::time::timeout_at(deadline, self.recv()).await {
+                Ok(response) => response?,
+                Err(_) => break,
+            };
+
+            match response {
+                Some(Response::Event {
+                    event: Event::Line { line },
+                }) => {
+                    capture.matched = until.is_some_and(|until| until.is_match(&line));
+                    capture.lines.push(line);
+
+                    if capture.matched {
+                        break;
+                    }
+                }
+                Some(Response::Event {
+                    event: Event::Exited { .. },
+                })
+                | None => {
+                    capture.exited = true;
+                    break;
+                }
+                Some(Response::Error { message }) => {
+                    return Err(std::io::Error::other(message));
+                }
+                Some(_) => continue,
+            }
+        }
+
+        Ok(capture)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::config::ConsoleLogConfig;
+
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+pub const DIRECTORY: &str = ".mcvcli/logs";
+
+/// The most lines `Snapshot::tail` returns, the count comes straight from clients.
+pub const MAX_TAIL: usize = 10_000;
+
+/// Appends console lines to `.mcvcli/logs/console.log`, rotating it into
+/// `console.1.log`, `console.2.log`, ... once it grows past the configured size.
+pub struct ConsoleLog {
+    directory: PathBuf,
+    max_size: u64,
+    max_files: usize,
+
+    file: Option<File>,
+    size: u64,
+    rotations: u64,
+}
+
+/// The end of the log at some point in time, the lines before it can be read without
+/// holding the lock of the log.
+pub struct Snapshot {
+    directory: PathBuf,
+    max_files: usize,
+    size: u64,
+    rotations: u64,
+}
+
+impl ConsoleLog {
+    pub fn new(config: &ConsoleLogConfig) -> Self {
+        Self {
+            directory: PathBuf::from(DIRECTORY),
+            max_size: config.max_size_mb as u64 * 1024 * 1024,
+            max_files: config.max_files as usize,
+            file: None,
+            size: 0,
+            rotations: 0,
+        }
+    }
+
+    #[inline]
+    fn path(&self, index: usize) -> PathBuf {
+        path(&self.directory, index)
+    }
+
+    fn open(&mut self) -> std::io::Result<&mut File> {
+        if self.file.is_none() {
+            std::fs::create_dir_all(&self.directory)?;
+
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.path(0))?;
+
+            self.size = file.metadata()?.len();
+            self.file = Some(file);
+        }
+
+        Ok(self.file.as_mut().unwrap())
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file = None;
+        self.rotations += 1;
+
+        std::fs::remove_file(self.path(self.max_files)).unwrap_or_default();
+        for index in (0..self.max_files).rev() {
+            if self.path(index).exists() {
+                std::fs::rename(self.path(index), self.path(index + 1))?;
+            }
+        }
+
+        if self.max_files == 0 {
+            std::fs::remove_file(self.path(0)).unwrap_or_default();
+        }
+
+        Ok(())
+    }
+
+    pub fn write(&mut self, line: &str) {
+        let line = format!(
+            "[{}] {}\n",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            line
+        );
+
+        if self.max_size > 0 && self.size > 0 && self.size + line.len() as u64 > self.max_size {
+            self.rotate().unwrap_or_default();
+        }
+
+        if let Ok(file) = self.open()
+            && file.write_all(line.as_bytes()).is_ok()
+        {
+            self.size += line.len() as u64;
+        }
+    }
+
+    #[inline]
+    pub fn rotations(&self) -> u64 {
+        self.rotations
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let size = if self.file.is_some() {
+            self.size
+        } else {
+            std::fs::metadata(self.path(0)).map_or(0, |metadata| metadata.len())
+        };
+
+        Snapshot {
+            directory: self.directory.clone(),
+            max_files: self.max_files,
+            size,
+            rotations: self.rotations,
+        }
+    }
+}
+
+impl Snapshot {
+    /// Returns up to `count` (at most `MAX_TAIL`) of the lines before the snapshot, oldest
+    /// first. Every rotation since the snapshot has moved the files up by one index.
+    pub fn tail(&self, count: usize, rotations: u64) -> Vec<String> {
+        let count = count.min(MAX_TAIL);
+        let shift = rotations.saturating_sub(self.rotations) as usize;
+        let mut lines = VecDeque::new();
+
+        for index in shift..=self.max_files {
+            if lines.len() >= count {
+                break;
+            }
+
+            let mut content = match std::fs::read(path(&self.directory, index)) {
+                Ok(content) => content,
+                Err(_) => break,
+            };
+
+            // lines written after the snapshot are delivered as events instead
+            if index == shift {
+                content.truncate(self.size as usize);
+            }
+
+            for line in String::from_utf8_lossy(&content).lines().rev() {
+                if lines.len() >= count {
+                    break;
+                }
+
+                lines.push_front(line.to_string());
+            }
+        }
+
+        lines.into()
+    }
+}
+
+#[inline]
+fn path(directory: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        directory.join("console.log")
+    } else {
+        directory.join(format!("console.{index}.log"))
+    }
+}
@@ -0,0 +1,1035 @@
+pub mod client;
+pub mod log;
+pub mod notify;
+pub mod performance;
+pub mod protocol;
+pub mod restart;
+pub mod schedule;
+pub mod tracker;
+pub mod watchdog;
+
+use crate::config::{Config, NotificationEvent, RconConfig, StopTransport};
+use crate::hooks::{self, Hook};
+use crate::limits::{self, Cgroup};
+use crate::players::Activity;
+use crate::rcon;
+use protocol::{Event, Request, Response, State};
+
+use colored::Colorize;
+use regex::Regex;
+use std::{
+    collections::VecDeque,
+    os::unix::fs::PermissionsExt,
+    path::PathBuf,
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicBool, AtomicI64, AtomicU32, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream},
+    process::{ChildStdin, Command},
+    sync::{Mutex, Notify, OnceCell, broadcast, mpsc},
+    task::JoinHandle,
+};
+
+/// The line servers print once they finished booting, the same for vanilla, paper,
+/// fabric, forge and neoforge: `Done (1.234s)! For help, type "help"`
+static READY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"Done \([\d.,]+s\)! For help, type "help""#).unwrap());
+
+pub struct Launch {
+    pub binary: String,
+    pub java_home: String,
+}
+
+/// Formats a duration for in-game messages, e.g. `5 minutes` or `30 seconds`.
+pub fn describe(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    let (amount, unit) = if seconds >= 3600 && seconds.is_multiple_of(3600) {
+        (seconds / 3600, "hour")
+    } else if seconds >= 60 && seconds.is_multiple_of(60) {
+        (seconds / 60, "minute")
+    } else {
+        (seconds, "second")
+    };
+
+    if amount == 1 {
+        format!("{amount} {unit}")
+    } else {
+        format!("{amount} {unit}s")
+    }
+}
+
+#[inline]
+pub fn socket_path(identifier: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("mcvcli_{identifier}.sock"))
+}
+
+pub fn command(config: &Config, launch: &Launch) -> Command {
+    let mut command = Command::new(&launch.binary);
+
+    command.args(&config.extra_flags);
+    command.arg(format!("-Xmx{}M", config.ram_mb));
+    command.arg("-jar");
+    command.arg(&config.jar_file);
+    command.arg("nogui");
+    command.args(&config.extra_args);
+    command.env("JAVA_HOME", &launch.java_home);
+    command.envs(config.environment());
+    command.stdin(std::process::Stdio::piped());
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+    command.kill_on_drop(true);
+    command.process_group(0);
+    limits::apply(&mut command, &config.limits);
+
+    command
+}
+
+/// Owns the java process of a server and shares its console with every
+/// client connected to the control socket.
+pub struct Supervisor {
+    config_path: String,
+    identifier: String,
+    stop_command: String,
+    rcon: Option<RconConfig>,
+    timeout: u64,
+
+    log: std::sync::Mutex<log::ConsoleLog>,
+    pid: std::sync::Mutex<Option<u32>>,
+    started: AtomicI64,
+    spawned: std::sync::Mutex<Instant>,
+    startup: std::sync::Mutex<Option<Duration>>,
+    stopping: AtomicBool,
+    restarting: AtomicBool,
+    /// Set once the supervisor got SIGTERM, the server stops even if pre_stop fails.
+    terminating: AtomicBool,
+    restarts: AtomicU32,
+    /// `MCVCLI_VERSION` and `MCVCLI_BUILD_ID` of the hooks, looked up on the first hook.
+    version: OnceCell<(String, String)>,
+
+    stdin: Mutex<Option<ChildStdin>>,
+    kill: Notify,
+    kill_timer: Mutex<Option<JoinHandle<()>>>,
+    countdown: Mutex<Option<(JoinHandle<()>, bool)>>,
+    events: broadcast::Sender<Event>,
+    notifications: std::sync::Mutex<Option<mpsc::UnboundedSender<notify::Notification>>>,
+    performance: Mutex<performance::Cache>,
+    tracker: tracker::Tracker,
+}
+
+impl Supervisor {
+    pub fn new(config: &Config, identifier: String, timeout: u64) -> Arc<Self> {
+        Arc::new(Self {
+            config_path: config.path().to_string(),
+            identifier,
+            stop_command: config.stop_command.clone(),
+            rcon: (config.stop_transport == StopTransport::Rcon).then(|| config.rcon.clone()),
+            timeout,
+            log: std::sync::Mutex::new(log::ConsoleLog::new(&config.console_log)),
+            pid: std::sync::Mutex::new(None),
+            started: AtomicI64::new(0),
+            spawned: std::sync::Mutex::new(Instant::now()),
+            startup: std::sync::Mutex::new(None),
+            stopping: AtomicBool::new(false),
+            restarting: AtomicBool::new(false),
+            terminating: AtomicBool::new(false),
+            restarts: AtomicU32::new(0),
+            version: OnceCell::new(),
+            stdin: Mutex::new(None),
+            kill: Notify::new(),
+            kill_timer: Mutex::new(None),
+            countdown: Mutex::new(None),
+            events: broadcast::channel(1024).0,
+            notifications: std::sync::Mutex::new(None),
+            performance: Mutex::new(performance::Cache::default()),
+            tracker: tracker::Tracker::default(),
+        })
+    }
+
+    #[inline]
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.events.subscribe()
+    }
+
+    /// Subscribes to events, returning the last `tail` logged lines that happened before.
+    pub fn subscribe_with_tail(&self, tail: usize) -> (Vec<String>, broadcast::Receiver<Event>) {
+        let (snapshot, events) = {
+            let log = self.log.lock().unwrap();
+            (log.snapshot(), self.events.subscribe())
+        };
+
+        (self.tail(&snapshot, tail), events)
+    }
+
+    /// Reads the lines before `snapshot` without holding the lock of the log.
+    fn tail(&self, snapshot: &log::Snapshot, count: usize) -> Vec<String> {
+        let rotations = self.log.lock().unwrap().rotations();
+
+        snapshot.tail(count, rotations)
+    }
+
+    /// Writes a console line to the log and broadcasts it to all subscribers.
+    pub fn line(&self, line: String) {
+        let mut log = self.log.lock().unwrap();
+        log.write(&line);
+
+        let _ = self.events.send(Event::Line { line });
+    }
+
+    /// Queues a webhook notification, dropped when the server is not running.
+    pub fn notify(&self, event: NotificationEvent, message: String) {
+        if let Some(sender) = self.notifications.lock().unwrap().as_ref() {
+            let _ = sender.send(notify::Notification {
+                event,
+                message,
+                time: chrono::Utc::now(),
+            });
+        }
+    }
+
+    pub fn state(&self) -> State {
+        let startup_ms = self
+            .startup
+            .lock()
+            .unwrap()
+            .map(|startup| startup.as_millis() as u64);
+
+        State {
+            supervisor_pid: std::process::id(),
+            pid: *self.pid.lock().unwrap(),
+            started: self.started.load(Ordering::SeqCst),
+            stopping: self.stopping.load(Ordering::SeqCst),
+            ready: startup_ms.is_some(),
+            startup_ms,
+            restarts: self.restarts.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Marks the server as ready once it printed its boot message.
+    fn detect_ready(self: &Arc<Self>, line: &str) {
+        let mut startup = self.startup.lock().unwrap();
+        if startup.is_some() || !READY.is_match(line) {
+            return;
+        }
+
+        let elapsed = self.spawned.lock().unwrap().elapsed();
+        *startup = Some(elapsed);
+        drop(startup);
+
+        self.line(
+            format!("server is ready after {:.1}s", elapsed.as_secs_f64())
+                .green()
+                .to_string(),
+        );
+        let _ = self.events.send(Event::Ready {
+            startup_ms: elapsed.as_millis() as u64,
+        });
+        self.notify(
+            NotificationEvent::Ready,
+            format!("server is ready after {:.1}s", elapsed.as_secs_f64()),
+        );
+
+        let supervisor = Arc::clone(self);
+        tokio::spawn(async move { supervisor.hook(Hook::PostStart, &[]).await });
+    }
+
+    /// Records player sessions and notifies about players joining or leaving.
+    fn detect_players(&self, line: &str) {
+        match self.track_player(line) {
+            Some(Activity::Join(name)) => self.notify(
+                NotificationEvent::PlayerJoin,
+                format!("{name} joined the game"),
+            ),
+            Some(Activity::Leave(name)) => self.notify(
+                NotificationEvent::PlayerLeave,
+                format!("{name} left the game"),
+            ),
+            _ => {}
+        }
+    }
+
+    /// Runs a hook, its output is written to the console like server output. Returns
+    /// whether it succeeded (or is not configured).
+    async fn hook(&self, hook: Hook, vars: &[(&str, String)]) -> bool {
+        let Some(config) = Config::new_optional(&self.config_path) else {
+            return true;
+        };
+        if !hook.is_configured(&config) {
+            return true;
+        }
+
+        let (version, build_id) = self
+            .version
+            .get_or_init(|| hooks::version(&config))
+            .await
+            .clone();
+        let vars = [("MCVCLI_VERSION", version), ("MCVCLI_BUILD_ID", build_id)]
+            .into_iter()
+            .chain(vars.iter().cloned())
+            .collect::<Vec<_>>();
+        let Some(mut command) = hooks::command(hook, &config, &vars).await else {
+            return true;
+        };
+
+        self.line(
+            format!("running {} hook ...", hook.name())
+                .bright_black()
+                .to_string(),
+        );
+
+        let output = tokio::time::timeout(
+            hooks::TIMEOUT,
+            command.stdin(std::process::Stdio::null()).output(),
+        )
+        .await;
+
+        match output {
+            Ok(Ok(output)) => {
+                for line in String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .chain(String::from_utf8_lossy(&output.stderr).lines())
+                {
+                    self.line(line.to_string());
+                }
+
+                if !output.status.success() {
+                    self.line(
+                        format!("{} hook has failed ({})", hook.name(), output.status)
+                            .red()
+                            .to_string(),
+                    );
+                }
+
+                output.status.success()
+            }
+            Ok(Err(err)) => {
+                self.line(
+                    format!("unable to run {} hook: {err}", hook.name())
+                        .red()
+                        .to_string(),
+                );
+
+                false
+            }
+            Err(_) => {
+                self.line(
+                    format!(
+                        "{} hook has timed out after {}s",
+                        hook.name(),
+                        hooks::TIMEOUT.as_secs()
+                    )
+                    .red()
+                    .to_string(),
+                );
+
+                false
+            }
+        }
+    }
+
+    /// Runs the pre_stop hook, a failing hook cancels the stop (or restart) unless the
+    /// supervisor is terminating.
+    async fn pre_stop(&self) -> std::io::Result<()> {
+        if self.hook(Hook::PreStop, &[]).await || self.terminating.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        self.stopping.store(false, Ordering::SeqCst);
+        self.restarting.store(false, Ordering::SeqCst);
+
+        Err(std::io::Error::other("pre_stop hook has failed"))
+    }
+
+    pub async fn command(&self, command: &str) -> std::io::Result<()> {
+        match self.stdin.lock().await.as_mut() {
+            Some(stdin) => {
+                stdin.write_all(format!("{command}\n").as_bytes()).await?;
+                stdin.flush().await
+            }
+            None => Err(std::io::Error::other("server is not running")),
+        }
+    }
+
+    /// Sends the stop command and kills the server if it is still alive after `timeout` seconds.
+    pub async fn stop(self: &Arc<Self>, timeout: u64) -> std::io::Result<()> {
+        self.restarting.store(false, Ordering::SeqCst);
+
+        if self.stopping.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        if self.stdin.lock().await.is_none() {
+            // waiting for a restart, wake up the run loop so it gives up
+            self.kill.notify_one();
+            return Ok(());
+        }
+
+        self.pre_stop().await?;
+        self.send_stop(timeout).await
+    }
+
+    /// Stops the server like [`Supervisor::stop`] for SIGTERM, neither a failing pre_stop
+    /// hook nor a failing stop command keep it running.
+    pub async fn terminate(self: &Arc<Self>, timeout: u64) {
+        self.terminating.store(true, Ordering::SeqCst);
+
+        if self.stop(timeout).await.is_err() {
+            self.kill.notify_one();
+        }
+    }
+
+    /// Stops the server like [`Supervisor::stop`] and starts it again once it has exited.
+    pub async fn restart(self: &Arc<Self>) -> std::io::Result<()> {
+        if self.stdin.lock().await.is_none() {
+            return Err(std::io::Error::other("server is not running"));
+        }
+
+        if self.stopping.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        self.restarting.store(true, Ordering::SeqCst);
+        self.pre_stop().await?;
+        self.send_stop(self.timeout).await
+    }
+
+    /// Kills the server without sending the stop command and starts it again.
+    pub async fn kill_and_restart(&self) {
+        if self.stdin.lock().await.is_none() || self.stopping.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        self.restarting.store(true, Ordering::SeqCst);
+        // the server is hanging, it is killed even if the hook fails
+        self.hook(Hook::PreStop, &[]).await;
+        self.kill.notify_one();
+    }
+
+    /// Broadcasts the stop (or restart) message of the countdown config for every warning,
+    /// largest first, and returns once the last warning has run out.
+    pub async fn countdown(&self, mut warnings: Vec<Duration>, restart: bool) {
+        let config = Config::new_optional(&self.config_path)
+            .map(|config| config.countdown)
+            .unwrap_or_default();
+        let message = if restart {
+            &config.restart_message
+        } else {
+            &config.stop_message
+        };
+
+        warnings.sort_unstable_by(|a, b| b.cmp(a));
+        warnings.dedup();
+
+        for (i, warning) in warnings.iter().enumerate() {
+            self.command(&config.format(&message.replace("{time}", &describe(*warning))))
+                .await
+                .unwrap_or_default();
+
+            let next = warnings.get(i + 1).copied().unwrap_or_default();
+            tokio::time::sleep(*warning - next).await;
+        }
+    }
+
+    /// Counts down `duration` in-game, saves the world and then stops (or restarts) the
+    /// server. The countdown can be cancelled with [`Supervisor::abort_countdown`].
+    pub async fn stop_countdown(
+        self: &Arc<Self>,
+        duration: Duration,
+        restart: bool,
+        timeout: u64,
+    ) -> std::io::Result<()> {
+        let warnings = Config::new_optional(&self.config_path)
+            .map(|config| config.countdown)
+            .unwrap_or_default()
+            .warnings(duration);
+
+        self.stop_countdown_with(duration, warnings, restart, timeout)
+            .await
+    }
+
+    /// Like [`Supervisor::stop_countdown`] with the given warnings instead of the ones of
+    /// the countdown config.
+    pub async fn stop_countdown_with(
+        self: &Arc<Self>,
+        duration: Duration,
+        warnings: Vec<Duration>,
+        restart: bool,
+        timeout: u64,
+    ) -> std::io::Result<()> {
+        if self.stdin.lock().await.is_none() {
+            return Err(std::io::Error::other("server is not running"));
+        }
+
+        let mut countdown = self.countdown.lock().await;
+        if countdown
+            .as_ref()
+            .is_some_and(|(task, _)| !task.is_finished())
+        {
+            return Err(std::io::Error::other("a countdown is already in progress"));
+        }
+
+        let supervisor = Arc::clone(self);
+        let task = tokio::spawn(async move {
+            supervisor.line(
+                format!(
+                    "{} the server in {} ...",
+                    if restart { "restarting" } else { "stopping" },
+                    describe(duration)
+                )
+                .yellow()
+                .to_string(),
+            );
+
+            supervisor.countdown(warnings, restart).await;
+
+            // past this point the countdown can no longer be aborted
+            supervisor.countdown.lock().await.take();
+
+            supervisor.save_all().await.unwrap_or_default();
+            if restart {
+                supervisor.restart().await.unwrap_or_default();
+            } else {
+                supervisor.stop(timeout).await.unwrap_or_default();
+            }
+        });
+
+        *countdown = Some((task, restart));
+
+        Ok(())
+    }
+
+    /// Cancels the countdown started by [`Supervisor::stop_countdown`].
+    pub async fn abort_countdown(&self) -> std::io::Result<()> {
+        let (task, restart) = match self.countdown.lock().await.take() {
+            Some((task, restart)) if !task.is_finished() => (task, restart),
+            _ => return Err(std::io::Error::other("no countdown is in progress")),
+        };
+
+        task.abort();
+
+        let config = Config::new_optional(&self.config_path)
+            .map(|config| config.countdown)
+            .unwrap_or_default();
+        let action = if restart { "restart" } else { "stop" };
+
+        self.command(&config.format(&config.abort_message.replace("{action}", action)))
+            .await
+            .unwrap_or_default();
+        self.line(
+            format!("{action} countdown has been aborted")
+                .yellow()
+                .to_string(),
+        );
+        let _ = self.events.send(Event::Aborted);
+
+        Ok(())
+    }
+
+    /// Runs `save-all flush` and waits (up to a minute) until the server has saved the world.
+    pub async fn save_all(&self) -> std::io::Result<()> {
+        let mut events = self.subscribe();
+        self.command("save-all flush").await?;
+
+        let _ = tokio::time::timeout(Duration::from_secs(60), async {
+            loop {
+                match events.recv().await {
+                    Ok(Event::Line { line }) if line.contains("Saved the game") => break,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                    _ => continue,
+                }
+            }
+        })
+        .await;
+
+        Ok(())
+    }
+
+    async fn send_stop_command(&self) -> std::io::Result<()> {
+        if let Some(config) = &self.rcon {
+            let result = match rcon::Address::resolve(config) {
+                Ok(address) => rcon::stop(&address, &self.stop_command).await,
+                Err(err) => Err(std::io::Error::other(err)),
+            };
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err(err) => self.line(
+                    format!("unable to stop the server over rcon ({err}), using the console ...")
+                        .yellow()
+                        .to_string(),
+                ),
+            }
+        }
+
+        self.command(&self.stop_command).await
+    }
+
+    async fn send_stop(self: &Arc<Self>, timeout: u64) -> std::io::Result<()> {
+        if let Err(err) = self.send_stop_command().await {
+            self.stopping.store(false, Ordering::SeqCst);
+            self.restarting.store(false, Ordering::SeqCst);
+            return Err(err);
+        }
+
+        let supervisor = Arc::clone(self);
+        *self.kill_timer.lock().await = Some(tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_secs(timeout)).await;
+
+            supervisor.line(
+                "server is taking too long to stop, killing it ..."
+                    .bright_black()
+                    .to_string(),
+            );
+            supervisor.kill.notify_one();
+        }));
+
+        Ok(())
+    }
+
+    pub fn listen(self: &Arc<Self>) -> std::io::Result<JoinHandle<()>> {
+        let path = socket_path(&self.identifier);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+
+        let listener = UnixListener::bind(&path)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+
+        let supervisor = Arc::clone(self);
+        Ok(tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(Arc::clone(&supervisor).handle(stream));
+            }
+        }))
+    }
+
+    /// Runs the server until it is stopped, restarting it according to the restart policy.
+    pub async fn run(self: &Arc<Self>, launch: &Launch) -> std::io::Result<Option<i32>> {
+        let mut restarts: VecDeque<Instant> = VecDeque::new();
+        let scheduler = tokio::spawn(schedule::run(Arc::clone(self)));
+        let watchdog = tokio::spawn(watchdog::run(Arc::clone(self)));
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        *self.notifications.lock().unwrap() = Some(sender);
+        let notifier = tokio::spawn(notify::run(Arc::clone(self), receiver));
+        let update = tokio::spawn(notify::check_update(Arc::clone(self)));
+
+        self.open_players().await;
+        let tracker = tokio::spawn(tracker::run(Arc::clone(self)));
+
+        let mut previous = None;
+        let code = loop {
+            // the first start ran the hook in `mcvcli start` already
+            if let Some(code) = previous
+                && !self.hook(Hook::PreStart, &[]).await
+            {
+                self.line(
+                    "not restarting the server, the pre_start hook has failed"
+                        .red()
+                        .to_string(),
+                );
+                break Ok(code);
+            }
+
+            let code = match self.run_once(launch).await {
+                Ok(code) => code,
+                Err(err) => break Err(err),
+            };
+            previous = Some(code);
+
+            if !self.stopping.load(Ordering::SeqCst) && code != Some(0) {
+                self.notify(
+                    NotificationEvent::Crashed,
+                    format!(
+                        "server has crashed with code {}",
+                        code.map(|code| code.to_string())
+                            .unwrap_or("unknown".to_string())
+                    ),
+                );
+                self.hook(
+                    Hook::OnCrash,
+                    &[(
+                        "MCVCLI_EXIT_CODE",
+                        code.map(|code| code.to_string()).unwrap_or_default(),
+                    )],
+                )
+                .await;
+            }
+
+            self.hook(
+                Hook::PostStop,
+                &[(
+                    "MCVCLI_EXIT_CODE",
+                    code.map(|code| code.to_string()).unwrap_or_default(),
+                )],
+            )
+            .await;
+
+            if self.restarting.swap(false, Ordering::SeqCst)
+                && !self.terminating.load(Ordering::SeqCst)
+            {
+                self.stopping.store(false, Ordering::SeqCst);
+                self.line("restarting server ...".yellow().to_string());
+                self.restarts.fetch_add(1, Ordering::SeqCst);
+
+                continue;
+            }
+
+            let config = match Config::new(&self.config_path, false) {
+                Ok(config) => config,
+                Err(err) => break Err(std::io::Error::other(err)),
+            };
+            let policy = &config.restart;
+
+            if self.stopping.load(Ordering::SeqCst) || !policy.policy.should_restart(code) {
+                break Ok(code);
+            }
+
+            let window = Duration::from_secs(policy.window_seconds);
+            while restarts
+                .front()
+                .is_some_and(|restart| restart.elapsed() > window)
+            {
+                restarts.pop_front();
+            }
+
+            let gave_up = restarts.len() >= policy.max_retries as usize;
+            if !gave_up {
+                restarts.push_back(Instant::now());
+            }
+
+            restart::record(restart::Restart {
+                time: chrono::Utc::now().timestamp(),
+                code,
+                attempt: restarts.len() as u32,
+                gave_up,
+                lines: {
+                    let snapshot = self.log.lock().unwrap().snapshot();
+                    self.tail(&snapshot, 10)
+                },
+            });
+
+            if gave_up {
+                self.notify(
+                    NotificationEvent::RestartLoop,
+                    format!(
+                        "server has crashed {} times within {}s, giving up",
+                        restarts.len() + 1,
+                        policy.window_seconds
+                    ),
+                );
+                self.line(
+                    format!(
+                        "server has crashed {} times within {}s, giving up",
+                        restarts.len() + 1,
+                        policy.window_seconds
+                    )
+                    .red()
+                    .to_string(),
+                );
+                break Ok(code);
+            }
+
+            let delay = policy.delay(restarts.len() as u32);
+            self.line(
+                format!(
+                    "server has exited with code {}, restarting in {}s (attempt {}/{}) ...",
+                    code.map(|code| code.to_string())
+                        .unwrap_or("unknown".to_string()),
+                    delay,
+                    restarts.len(),
+                    policy.max_retries
+                )
+                .yellow()
+                .to_string(),
+            );
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(delay)) => {}
+                _ = self.kill.notified() => {}
+            }
+
+            if self.stopping.load(Ordering::SeqCst) {
+                break Ok(code);
+            }
+
+            self.restarts.fetch_add(1, Ordering::SeqCst);
+        };
+
+        scheduler.abort();
+        watchdog.abort();
+        update.abort();
+        tracker.abort();
+        self.close_tracker().await;
+
+        let code = match code {
+            Ok(code) => code,
+            Err(err) => {
+                self.notifications.lock().unwrap().take();
+                return Err(err);
+            }
+        };
+
+        self.notify(
+            NotificationEvent::Stopped,
+            format!(
+                "server has stopped with code {}",
+                code.map(|code| code.to_string())
+                    .unwrap_or("unknown".to_string())
+            ),
+        );
+        // let the queued notifications go out before the supervisor exits
+        self.notifications.lock().unwrap().take();
+        let _ = tokio::time::timeout(Duration::from_secs(30), notifier).await;
+        let _ = self.events.send(Event::Exited { code });
+
+        Ok(code)
+    }
+
+    /// Spawns the server and waits for it to exit, returns the exit code.
+    async fn run_once(self: &Arc<Self>, launch: &Launch) -> std::io::Result<Option<i32>> {
+        let mut config = Config::new(&self.config_path, false).map_err(std::io::Error::other)?;
+        if let Err(err) = config.env_file() {
+            self.line(format!("{err}, starting without it").yellow().to_string());
+        }
+
+        let cgroup = Cgroup::create(&format!("mcvcli-{}", self.identifier), &config.limits)
+            .unwrap_or_else(|err| {
+                self.line(
+                    format!("unable to apply the cgroup limits ({err})")
+                        .yellow()
+                        .to_string(),
+                );
+                None
+            });
+
+        // sessions left open by a supervisor that did not exit cleanly
+        self.close_players(None);
+
+        let mut child = command(&config, launch).spawn()?;
+
+        if let Some(cgroup) = &cgroup
+            && let Some(pid) = child.id()
+            && let Err(err) = cgroup.add(pid)
+        {
+            self.line(
+                format!("unable to apply the cgroup limits ({err})")
+                    .yellow()
+                    .to_string(),
+            );
+        }
+
+        *self.stdin.lock().await = child.stdin.take();
+        *self.pid.lock().unwrap() = child.id();
+        *self.spawned.lock().unwrap() = Instant::now();
+        *self.startup.lock().unwrap() = None;
+        *self.performance.lock().await = performance::Cache::default();
+        self.started
+            .store(chrono::Utc::now().timestamp(), Ordering::SeqCst);
+
+        config.pid = child.id().map(|pid| pid as usize);
+        config.identifier = Some(self.identifier.clone());
+        config.save()?;
+
+        self.notify(
+            NotificationEvent::Started,
+            format!(
+                "server has started (pid {})",
+                child.id().map(|pid| pid.to_string()).unwrap_or_default()
+            ),
+        );
+
+        let readers = [
+            child.stdout.take().map(|stdout| self.forward(stdout)),
+            child.stderr.take().map(|stderr| self.forward(stderr)),
+        ];
+
+        let status = loop {
+            tokio::select! {
+                status = child.wait() => break status?,
+                _ = self.kill.notified() => {
+                    // ignore kill requests left over from a previous run
+                    if self.stopping.load(Ordering::SeqCst) {
+                        child.kill().await?;
+                        break child.wait().await?;
+                    }
+                }
+            }
+        };
+
+        for reader in readers.into_iter().flatten() {
+            reader.await.unwrap_or_default();
+        }
+
+        if let Some(kill_timer) = self.kill_timer.lock().await.take() {
+            kill_timer.abort();
+        }
+
+        self.stdin.lock().await.take();
+        *self.pid.lock().unwrap() = None;
+        *self.startup.lock().unwrap() = None;
+        drop(cgroup);
+        self.close_players(Some(chrono::Utc::now().timestamp()));
+
+        let _ = self.events.send(Event::ProcessExited {
+            code: status.code(),
+        });
+
+        Ok(status.code())
+    }
+
+    /// Removes the control socket and clears the process information from the config.
+    pub fn shutdown(&self) {
+        std::fs::remove_file(socket_path(&self.identifier)).unwrap_or_default();
+
+        if let Some(mut config) = Config::new_optional(&self.config_path)
+            && config.identifier.as_ref() == Some(&self.identifier)
+        {
+            config.pid = None;
+            config.identifier = None;
+            config.save().unwrap_or_default();
+        }
+    }
+
+    fn forward<R>(self: &Arc<Self>, reader: R) -> JoinHandle<()>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let supervisor = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(reader);
+            let mut buffer = Vec::new();
+
+            loop {
+                buffer.clear();
+
+                match reader.read_until(b'\n', &mut buffer).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        let line = String::from_utf8_lossy(&buffer)
+                            .trim_end_matches(['\r', '\n'])
+                            .to_string();
+
+                        supervisor.line(line.clone());
+                        supervisor.detect_ready(&line);
+                        supervisor.detect_players(&line);
+                    }
+                }
+            }
+        })
+    }
+
+    async fn handle(self: Arc<Self>, stream: UnixStream) {
+        let (mut reader, mut writer) = stream.into_split();
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Response>();
+
+        let writer = tokio::spawn(async move {
+            while let Some(response) = receiver.recv().await {
+                if protocol::write(&mut writer, &response).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut subscription = None;
+        while let Ok(Some(request)) = protocol::read::<_, Request>(&mut reader).await {
+            let response = match request {
+                Request::Command { command } => match self.command(&command).await {
+                    Ok(()) => Response::Ok,
+                    Err(err) => Response::Error {
+                        message: err.to_string(),
+                    },
+                },
+                Request::Subscribe { tail } => {
+                    if subscription.is_none() {
+                        let (history, mut events) = self.subscribe_with_tail(tail);
+                        let sender = sender.clone();
+
+                        for line in history {
+                            sender
+                                .send(Response::Event {
+                                    event: Event::Line { line },
+                                })
+                                .unwrap_or_default();
+                        }
+
+                        subscription = Some(tokio::spawn(async move {
+                            loop {
+                                match events.recv().await {
+                                    Ok(event) => {
+                                        if sender.send(Response::Event { event }).is_err() {
+                                            break;
+                                        }
+                                    }
+                                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                                    Err(broadcast::error::RecvError::Closed) => break,
+                                }
+                            }
+                        }));
+                    }
+
+                    Response::Ok
+                }
+                Request::State => Response::State {
+                    state: self.state(),
+                },
+                Request::Performance => Response::Performance {
+                    performance: self.performance().await,
+                },
+                Request::Stop { timeout, countdown } => {
+                    let result = if countdown > 0 {
+                        self.stop_countdown(Duration::from_secs(countdown), false, timeout)
+                            .await
+                    } else {
+                        self.stop(timeout).await
+                    };
+
+                    match result {
+                        Ok(()) => Response::Ok,
+                        Err(err) => Response::Error {
+                            message: err.to_string(),
+                        },
+                    }
+                }
+                Request::Restart { countdown } => {
+                    let result = if countdown > 0 {
+                        self.stop_countdown(Duration::from_secs(countdown), true, self.timeout)
+                            .await
+                    } else {
+                        self.restart().await
+                    };
+
+                    match result {
+                        Ok(()) => Response::Ok,
+                        Err(err) => Response::Error {
+                            message: err.to_string(),
+                        },
+                    }
+                }
+                Request::Abort => match self.abort_countdown().await {
+                    Ok(()) => Response::Ok,
+                    Err(err) => Response::Error {
+                        message: err.to_string(),
+                    },
+                },
+            };
+
+            if sender.send(response).is_err() {
+                break;
+            }
+        }
+
+        if let Some(subscription) = subscription {
+            subscription.abort();
+        }
+
+        drop(sender);
+        writer.await.unwrap_or_default();
+    }
+}
@@ -0,0 +1,282 @@
+use super::Supervisor;
+use crate::config::{Config, NotificationEvent, WebhookConfig, WebhookFormat};
+use crate::{api, jar, registry};
+
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+/// The longest delay between two attempts, also caps the `Retry-After` of rate limits.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub message: String,
+    pub time: DateTime<Utc>,
+}
+
+impl NotificationEvent {
+    pub fn name(self) -> &'static str {
+        match self {
+            NotificationEvent::Started => "started",
+            NotificationEvent::Ready => "ready",
+            NotificationEvent::Stopped => "stopped",
+            NotificationEvent::Crashed => "crashed",
+            NotificationEvent::RestartLoop => "restart-loop",
+            NotificationEvent::PlayerJoin => "player-join",
+            NotificationEvent::PlayerLeave => "player-leave",
+            NotificationEvent::UpdateAvailable => "update-available",
+            NotificationEvent::BackupFinished => "backup-finished",
+        }
+    }
+
+    /// The color of discord embeds.
+    fn color(self) -> u32 {
+        match self {
+            NotificationEvent::Started | NotificationEvent::Ready => 0x57f287,
+            NotificationEvent::Stopped => 0x95a5a6,
+            NotificationEvent::Crashed | NotificationEvent::RestartLoop => 0xed4245,
+            NotificationEvent::PlayerJoin | NotificationEvent::PlayerLeave => 0x5865f2,
+            NotificationEvent::UpdateAvailable => 0xfee75c,
+            NotificationEvent::BackupFinished => 0x3498db,
+        }
+    }
+}
+
+/// Escapes a value for use inside a JSON string of a template.
+fn escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap();
+
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+fn payload(
+    webhook: &WebhookConfig,
+    server: &str,
+    profile: &str,
+    notification: &Notification,
+) -> Result<Value, serde_json::Error> {
+    let event = notification.event.name();
+    let time = notification.time.to_rfc3339();
+
+    if let Some(template) = &webhook.template {
+        return serde_json::from_str(
+            &template
+                .replace("{event}", &escape(event))
+                .replace("{server}", &escape(server))
+                .replace("{profile}", &escape(profile))
+                .replace("{message}", &escape(&notification.message))
+                .replace("{time}", &escape(&time)),
+        );
+    }
+
+    Ok(match webhook.format {
+        WebhookFormat::Generic => json!({
+            "event": event,
+            "server": server,
+            "profile": profile,
+            "message": notification.message,
+            "time": time,
+        }),
+        WebhookFormat::Discord => json!({
+            "username": "mcvcli",
+            "embeds": [{
+                "title": format!("{server} ({profile})"),
+                "description": notification.message,
+                "color": notification.event.color(),
+                "footer": { "text": event },
+                "timestamp": time,
+            }],
+        }),
+        WebhookFormat::Slack => json!({
+            "text": format!("*{server}* ({profile}): {}", notification.message),
+        }),
+    })
+}
+
+/// Posts the payload, retrying server errors, timeouts and rate limits up to `retries` times.
+async fn send(url: &str, payload: &Value, retries: u32) -> Result<(), String> {
+    let mut attempt = 0;
+
+    loop {
+        let backoff = Duration::from_secs(1 << attempt.min(6)).min(MAX_DELAY);
+        let (delay, error) = match api::CLIENT
+            .post(url)
+            .json(payload)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => (
+                response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse::<f64>().ok())
+                    .and_then(|value| Duration::try_from_secs_f64(value).ok())
+                    .map_or(backoff, |delay| delay.min(MAX_DELAY)),
+                response.status().to_string(),
+            ),
+            Ok(response) if response.status().is_server_error() => {
+                (backoff, response.status().to_string())
+            }
+            Ok(response) => return Err(response.status().to_string()),
+            Err(err) => (backoff, err.to_string()),
+        };
+
+        if attempt >= retries {
+            return Err(error);
+        }
+
+        attempt += 1;
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// The host of a webhook url, the rest usually contains its secret.
+fn host(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or("webhook".to_string())
+}
+
+/// A notification ready to be posted to a single webhook.
+struct Delivery {
+    event: NotificationEvent,
+    payload: Value,
+    retries: u32,
+}
+
+/// Posts the deliveries of one webhook in order, so a slow or rate limited webhook
+/// does not hold up the others.
+fn worker(
+    supervisor: Arc<Supervisor>,
+    url: String,
+) -> (mpsc::UnboundedSender<Delivery>, JoinHandle<()>) {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<Delivery>();
+
+    let handle = tokio::spawn(async move {
+        while let Some(delivery) = receiver.recv().await {
+            if let Err(err) = send(&url, &delivery.payload, delivery.retries).await {
+                supervisor.line(
+                    format!(
+                        "unable to send {} notification to {}: {err}",
+                        delivery.event.name(),
+                        host(&url)
+                    )
+                    .yellow()
+                    .to_string(),
+                );
+            }
+        }
+    });
+
+    (sender, handle)
+}
+
+/// Sends the notifications to the webhooks of the config, in order per webhook and at
+/// most `rateLimitPerMinute` per webhook, until the sender is dropped.
+pub async fn run(supervisor: Arc<Supervisor>, mut receiver: mpsc::UnboundedReceiver<Notification>) {
+    let server = registry::name(&std::env::current_dir().unwrap_or_default());
+    let mut sent: HashMap<String, VecDeque<Instant>> = HashMap::new();
+    let mut workers: HashMap<String, (mpsc::UnboundedSender<Delivery>, JoinHandle<()>)> =
+        HashMap::new();
+
+    while let Some(notification) = receiver.recv().await {
+        let Some(config) = Config::new_optional(&supervisor.config_path) else {
+            continue;
+        };
+        let notifications = &config.notifications;
+
+        for webhook in notifications
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.wants(notification.event))
+        {
+            let sent = sent.entry(webhook.url.clone()).or_default();
+            while sent
+                .front()
+                .is_some_and(|time| time.elapsed() > Duration::from_secs(60))
+            {
+                sent.pop_front();
+            }
+
+            if sent.len() >= notifications.rate_limit_per_minute as usize {
+                continue;
+            }
+            sent.push_back(Instant::now());
+
+            let payload = match payload(webhook, &server, &config.profile_name, &notification) {
+                Ok(payload) => payload,
+                Err(err) => {
+                    supervisor.line(
+                        format!("invalid template of webhook {}: {err}", host(&webhook.url))
+                            .yellow()
+                            .to_string(),
+                    );
+                    continue;
+                }
+            };
+
+            let (sender, _) = workers
+                .entry(webhook.url.clone())
+                .or_insert_with(|| worker(Arc::clone(&supervisor), webhook.url.clone()));
+            let _ = sender.send(Delivery {
+                event: notification.event,
+                payload,
+                retries: notifications.retries,
+            });
+        }
+    }
+
+    // let the workers drain their queues
+    for (_, (sender, handle)) in workers {
+        drop(sender);
+        let _ = handle.await;
+    }
+}
+
+/// Notifies once if a newer build of the installed version is available.
+pub async fn check_update(supervisor: Arc<Supervisor>) {
+    let Some(config) = Config::new_optional(&supervisor.config_path) else {
+        return;
+    };
+    if !config
+        .notifications
+        .wants(NotificationEvent::UpdateAvailable)
+    {
+        return;
+    }
+
+    let Ok(Some(([build, latest], _, _))) =
+        tokio::time::timeout(Duration::from_secs(60), jar::detect(".", &config)).await
+    else {
+        return;
+    };
+
+    if build.id != latest.id {
+        supervisor.notify(
+            NotificationEvent::UpdateAvailable,
+            format!(
+                "build {} of {} {} is available, {} is installed (mcvcli update)",
+                latest.name,
+                build.r#type,
+                build
+                    .version_id
+                    .or(build.project_version_id)
+                    .unwrap_or_default(),
+                build.name
+            ),
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use super::{Supervisor, protocol::Event};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+use tokio::sync::broadcast::error::RecvError;
+
+static FORMATTING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m|§.").unwrap());
+static TPS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"TPS from last 1m, 5m, 15m: \*?([\d.]+), \*?([\d.]+), \*?([\d.]+)").unwrap()
+});
+static MSPT_HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Server tick times \(avg/min/max\) from last 5s, 10s, 1m:").unwrap()
+});
+static MSPT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"([\d.]+)/[\d.]+/[\d.]+, [\d.]+/[\d.]+/[\d.]+, [\d.]+/[\d.]+/[\d.]+\s*$").unwrap()
+});
+static UNKNOWN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Unknown (or incomplete )?command").unwrap());
+
+/// How long a measurement is reused, every scrape and `status` call would run the
+/// commands in the console otherwise.
+const TTL: Duration = Duration::from_secs(30);
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The tps and mspt reported by paper and its forks.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Performance {
+    pub tps: Option<[f64; 3]>,
+    pub mspt: Option<f64>,
+}
+
+#[derive(Default)]
+pub struct Cache {
+    performance: Performance,
+    updated: Option<Instant>,
+    /// The server answered `tps` or `mspt` with "Unknown command" pointing at the command
+    /// (`tps<--[HERE]`), it is not probed again.
+    no_tps: bool,
+    no_mspt: bool,
+}
+
+/// The result of [`Supervisor::probe`].
+enum Reply {
+    Lines(Vec<String>),
+    /// An "Unknown command" line was printed, `confirmed` if it was the reply to the
+    /// command of the probe and not to a command typed in the console at the same time.
+    Unknown {
+        confirmed: bool,
+    },
+}
+
+#[inline]
+fn strip_formatting(line: &str) -> String {
+    FORMATTING.replace_all(line, "").to_string()
+}
+
+/// Parses the output of the paper/spigot `tps` command.
+fn parse_tps(lines: &[String]) -> Option<[f64; 3]> {
+    lines.iter().find_map(|line| {
+        let captures = TPS.captures(line)?;
+
+        Some([
+            captures[1].parse().ok()?,
+            captures[2].parse().ok()?,
+            captures[3].parse().ok()?,
+        ])
+    })
+}
+
+/// Parses the average tick time of the last 5s from the line after the header of the
+/// paper `mspt` command.
+fn parse_mspt(lines: &[String]) -> Option<f64> {
+    let header = lines.iter().position(|line| MSPT_HEADER.is_match(line))?;
+
+    MSPT.captures(lines.get(header + 1)?)?[1].parse().ok()
+}
+
+impl Supervisor {
+    /// Returns the cached tps and mspt, running the commands again once the cache expired.
+    /// Nothing is sent before the server is ready or to servers that do not know them.
+    pub async fn performance(&self) -> Performance {
+        let mut cache = self.performance.lock().await;
+
+        if self.startup.lock().unwrap().is_none() {
+            return Performance::default();
+        }
+        if cache.updated.is_some_and(|updated| updated.elapsed() < TTL) {
+            return cache.performance;
+        }
+
+        cache.performance.tps = None;
+        if !cache.no_tps {
+            match self.probe("tps", &TPS).await {
+                Reply::Lines(lines) => cache.performance.tps = parse_tps(&lines),
+                Reply::Unknown { confirmed } => cache.no_tps = confirmed,
+            }
+        }
+
+        cache.performance.mspt = None;
+        if !cache.no_mspt {
+            match self.probe("mspt", &MSPT).await {
+                Reply::Lines(lines) => cache.performance.mspt = parse_mspt(&lines),
+                Reply::Unknown { confirmed } => cache.no_mspt = confirmed,
+            }
+        }
+
+        cache.updated = Some(Instant::now());
+
+        cache.performance
+    }
+
+    /// Runs a command and collects the following lines until one matches `until`.
+    async fn probe(&self, command: &str, until: &Regex) -> Reply {
+        let mut events = self.subscribe();
+        let mut lines = Vec::new();
+        let mut unknown = false;
+
+        if self.command(command).await.is_err() {
+            return Reply::Lines(lines);
+        }
+
+        let deadline = tokio::time::Instant::now() + TIMEOUT;
+
+        loop {
+            match tokio::time::timeout_at(deadline, events.recv()).await {
+                Ok(Ok(Event::Line { line })) => {
+                    let line = strip_formatting(&line);
+                    if UNKNOWN.is_match(&line) {
+                        unknown = true;
+                        continue;
+                    }
+
+                    // vanilla points at the command it did not understand
+                    if unknown && line.trim_end().ends_with(&format!("{command}<--[HERE]")) {
+                        return Reply::Unknown { confirmed: true };
+                    }
+
+                    let matched = until.is_match(&line);
+                    lines.push(line);
+
+                    if matched {
+                        return Reply::Lines(lines);
+                    }
+                }
+                Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => continue,
+                Ok(Err(RecvError::Closed)) | Err(_) => break,
+            }
+        }
+
+        if unknown {
+            Reply::Unknown { confirmed: false }
+        } else {
+            Reply::Lines(lines)
+        }
+    }
+}
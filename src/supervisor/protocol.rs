@@ -0,0 +1,118 @@
+use super::performance::Performance;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    Command {
+        command: String,
+    },
+    Subscribe {
+        #[serde(default)]
+        tail: usize,
+    },
+    State,
+    /// The cached tps and mspt of the server, see `Supervisor::performance`.
+    Performance,
+    Stop {
+        timeout: u64,
+        /// Seconds to count down in-game before stopping, `0` stops right away.
+        #[serde(default)]
+        countdown: u64,
+    },
+    Restart {
+        #[serde(default)]
+        countdown: u64,
+    },
+    /// Cancels a stop or restart countdown.
+    Abort,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Ok,
+    Error { message: String },
+    State { state: State },
+    Performance { performance: Performance },
+    Event { event: Event },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    Line {
+        line: String,
+    },
+    /// The server finished booting, `startup_ms` is measured from spawning the process.
+    Ready {
+        startup_ms: u64,
+    },
+    /// The server process exited, the supervisor may still restart it.
+    ProcessExited {
+        code: Option<i32>,
+    },
+    /// A stop or restart countdown has been cancelled.
+    Aborted,
+    /// The supervisor is done and will not restart the server again.
+    Exited {
+        code: Option<i32>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    pub supervisor_pid: u32,
+    pub pid: Option<u32>,
+    pub started: i64,
+    pub stopping: bool,
+    #[serde(default)]
+    pub ready: bool,
+    #[serde(default)]
+    pub startup_ms: Option<u64>,
+    /// The times the java process was restarted since the supervisor started.
+    #[serde(default)]
+    pub restarts: u32,
+}
+
+/// Writes a single frame: a big endian `u32` length followed by the JSON payload.
+pub async fn write<W, T>(writer: &mut W, value: &T) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let payload = serde_json::to_vec(value)?;
+
+    writer.write_u32(payload.len() as u32).await?;
+    writer.write_all(&payload).await?;
+    writer.flush().await
+}
+
+/// Reads a single frame, returns `None` once the other side closed the connection.
+pub async fn read<R, T>(reader: &mut R) -> std::io::Result<Option<T>>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let length = match reader.read_u32().await {
+        Ok(length) => length as usize,
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    if length > MAX_FRAME_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("frame of {length} bytes exceeds the maximum frame size"),
+        ));
+    }
+
+    let mut payload = vec![0; length];
+    reader.read_exact(&mut payload).await?;
+
+    Ok(Some(serde_json::from_slice(&payload)?))
+}
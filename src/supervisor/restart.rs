@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+
+pub const FILE: &str = ".mcvcli/restarts.json";
+const MAX_HISTORY: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Restart {
+    pub time: i64,
+    pub code: Option<i32>,
+    pub attempt: u32,
+    pub gave_up: bool,
+    pub lines: Vec<String>,
+}
+
+pub fn history() -> Vec<Restart> {
+    match File::open(FILE) {
+        Ok(file) => serde_json::from_reader(file).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn record(restart: Restart) {
+    let mut history = history();
+    history.push(restart);
+
+    if history.len() > MAX_HISTORY {
+        history.drain(..history.len() - MAX_HISTORY);
+    }
+
+    std::fs::create_dir_all(".mcvcli").unwrap_or_default();
+    if let Ok(file) = File::create(FILE) {
+        serde_json::to_writer_pretty(file, &history).unwrap_or_default();
+    }
+}
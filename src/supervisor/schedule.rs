@@ -0,0 +1,128 @@
+use super::Supervisor;
+use crate::backup;
+use crate::config::{Config, NotificationEvent, ScheduleAction, ScheduleEntry};
+
+use chrono::{DateTime, Local, Timelike};
+use colored::Colorize;
+use croner::{Cron, errors::CronError};
+use std::{sync::Arc, time::Duration};
+
+#[inline]
+pub fn parse(cron: &str) -> Result<Cron, CronError> {
+    Cron::new(cron).parse()
+}
+
+pub fn next(entry: &ScheduleEntry) -> Option<DateTime<Local>> {
+    parse(&entry.cron)
+        .ok()?
+        .find_next_occurrence(&Local::now(), false)
+        .ok()
+}
+
+/// Checks the schedule of the config once a minute and runs every entry that became due.
+pub async fn run(supervisor: Arc<Supervisor>) {
+    let mut last = Local::now();
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(60 - Local::now().second() as u64)).await;
+
+        let now = Local::now();
+        let config = match Config::new_optional(&supervisor.config_path) {
+            Some(config) => config,
+            None => continue,
+        };
+
+        for entry in config.schedule {
+            let cron = match parse(&entry.cron) {
+                Ok(cron) => cron,
+                Err(_) => continue,
+            };
+
+            if cron
+                .find_next_occurrence(&last, false)
+                .is_ok_and(|next| next <= now)
+            {
+                tokio::spawn(execute(Arc::clone(&supervisor), entry));
+            }
+        }
+
+        last = now;
+    }
+}
+
+async fn execute(supervisor: Arc<Supervisor>, entry: ScheduleEntry) {
+    supervisor.line(
+        format!("running scheduled task {} ...", entry.name)
+            .bright_black()
+            .to_string(),
+    );
+
+    match entry.action {
+        ScheduleAction::Command { command } => {
+            supervisor.command(&command).await.unwrap_or_default();
+        }
+        ScheduleAction::Restart { warnings } => {
+            let warnings = warnings
+                .iter()
+                .filter_map(|warning| humantime::parse_duration(warning).ok())
+                .collect::<Vec<_>>();
+            let duration = warnings.iter().max().copied().unwrap_or_default();
+
+            // like `mcvcli restart --countdown`, so it can be aborted and saves the world first
+            if let Err(err) = supervisor
+                .stop_countdown_with(duration, warnings, true, supervisor.timeout)
+                .await
+            {
+                supervisor.line(
+                    format!("scheduled restart {} skipped: {err}", entry.name)
+                        .yellow()
+                        .to_string(),
+                );
+            }
+        }
+        ScheduleAction::Backup { keep } => {
+            self::backup(&supervisor, &entry.name, keep).await;
+        }
+    }
+}
+
+async fn backup(supervisor: &Arc<Supervisor>, name: &str, keep: Option<usize>) {
+    let running = supervisor.command("save-off").await.is_ok();
+
+    if running {
+        supervisor.save_all().await.unwrap_or_default();
+    }
+
+    let result = tokio::task::spawn_blocking({
+        let name = name.to_string();
+
+        move || backup::create(&name, keep)
+    })
+    .await;
+
+    if running {
+        supervisor.command("save-on").await.unwrap_or_default();
+    }
+
+    match result {
+        Ok(Ok(path)) => {
+            supervisor.line(
+                format!("backup created at {}", path.display())
+                    .green()
+                    .to_string(),
+            );
+            supervisor.notify(
+                NotificationEvent::BackupFinished,
+                format!("backup {name} created at {}", path.display()),
+            );
+        }
+        Ok(Err(err)) => {
+            supervisor.line(format!("backup failed: {err}").red().to_string());
+            supervisor.notify(
+                NotificationEvent::BackupFinished,
+                format!("backup {name} failed: {err}"),
+            );
+        }
+        Err(_) => supervisor.line("backup failed".red().to_string()),
+    }
+}
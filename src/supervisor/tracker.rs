@@ -0,0 +1,104 @@
+use super::Supervisor;
+use crate::players::{self, Activity, Store};
+
+use std::{fs::File, sync::Arc, time::Duration};
+use tokio::sync::Notify;
+
+/// How often chats and deaths are written to `.mcvcli/players.json`, joins and leaves
+/// are written right away.
+const INTERVAL: Duration = Duration::from_secs(30);
+
+/// The player sessions, kept in memory while the supervisor runs instead of loading and
+/// saving the store for every console line.
+#[derive(Default)]
+pub struct Tracker {
+    state: std::sync::Mutex<State>,
+    save: Notify,
+}
+
+#[derive(Default)]
+struct State {
+    store: Store,
+    changed: bool,
+    /// Keeps the cli from saving imports the supervisor would overwrite.
+    lock: Option<File>,
+}
+
+impl Supervisor {
+    /// Takes over the store, waits for a cli that is importing logs right now.
+    pub(super) async fn open_players(&self) {
+        let (lock, store) = tokio::task::spawn_blocking(|| (players::lock(true), Store::load()))
+            .await
+            .unwrap_or_default();
+
+        *self.tracker.state.lock().unwrap() = State {
+            store,
+            changed: false,
+            lock,
+        };
+    }
+
+    /// Records a line of the console, returns the activity if it was one.
+    pub(super) fn track_player(&self, line: &str) -> Option<Activity> {
+        let activity = players::activity(line)?;
+
+        let mut state = self.tracker.state.lock().unwrap();
+        if state
+            .store
+            .record(chrono::Utc::now().timestamp(), &activity)
+        {
+            state.changed = true;
+
+            if matches!(activity, Activity::Join(_) | Activity::Leave(_)) {
+                self.tracker.save.notify_one();
+            }
+        }
+
+        Some(activity)
+    }
+
+    /// Ends all open sessions, the server has stopped.
+    pub(super) fn close_players(&self, time: Option<i64>) {
+        let mut state = self.tracker.state.lock().unwrap();
+        if state.store.close(time) {
+            state.changed = true;
+            self.tracker.save.notify_one();
+        }
+    }
+
+    /// Writes the store if it changed since the last time.
+    pub(super) async fn save_players(&self) {
+        let content = {
+            let mut state = self.tracker.state.lock().unwrap();
+            if !state.changed {
+                return;
+            }
+
+            state.changed = false;
+            serde_json::to_vec(&state.store)
+        };
+
+        if let Ok(content) = content {
+            tokio::task::spawn_blocking(move || players::write(&content))
+                .await
+                .unwrap_or_default();
+        }
+    }
+
+    /// Releases the store to the cli again.
+    pub(super) async fn close_tracker(&self) {
+        self.save_players().await;
+        self.tracker.state.lock().unwrap().lock.take();
+    }
+}
+
+pub async fn run(supervisor: Arc<Supervisor>) {
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(INTERVAL) => {}
+            _ = supervisor.tracker.save.notified() => {}
+        }
+
+        supervisor.save_players().await;
+    }
+}
@@ -0,0 +1,232 @@
+use super::{Supervisor, protocol::Event};
+use crate::config::{Config, WatchdogConfig};
+use crate::properties::{self, Properties};
+
+use chrono::Local;
+use colored::Colorize;
+use std::{
+    collections::VecDeque,
+    io::Write,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use sysinfo::{ProcessesToUpdate, Signal};
+use tokio::sync::broadcast;
+
+pub const DIRECTORY: &str = ".mcvcli/watchdog";
+
+/// Pings the server every `intervalSeconds` and kills and restarts it once
+/// `maxFailures` pings in a row went unanswered. Incidents count towards the
+/// `maxRetries` within `windowSeconds` of the restart policy.
+pub async fn run(supervisor: Arc<Supervisor>) {
+    let mut interval = WatchdogConfig::default().interval_seconds;
+    let mut started = 0;
+    let mut failures = 0;
+    let mut incidents: VecDeque<Instant> = VecDeque::new();
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+
+        let config = match Config::new_optional(&supervisor.config_path) {
+            Some(config) => config,
+            None => continue,
+        };
+        let watchdog = config.watchdog;
+        interval = watchdog.interval_seconds.max(1);
+
+        let state = supervisor.state();
+        let Some(pid) = state.pid else {
+            failures = 0;
+            continue;
+        };
+
+        if !watchdog.enabled || state.stopping || state.started != started {
+            started = state.started;
+            failures = 0;
+            continue;
+        }
+
+        // servers are expected to be unreachable while booting
+        if !state.ready
+            && supervisor.spawned.lock().unwrap().elapsed()
+                < Duration::from_secs(watchdog.grace_seconds)
+        {
+            continue;
+        }
+
+        let (host, port) = Properties::new(properties::FILE)
+            .unwrap_or_default()
+            .server_address();
+
+        if ping(&host, port, Duration::from_secs(watchdog.timeout_seconds)).await {
+            failures = 0;
+            continue;
+        }
+
+        failures += 1;
+        supervisor.line(
+            format!(
+                "watchdog: server did not answer a ping on {host}:{port} ({failures}/{})",
+                watchdog.max_failures
+            )
+            .yellow()
+            .to_string(),
+        );
+
+        if failures < watchdog.max_failures {
+            continue;
+        }
+        failures = 0;
+
+        let window = Duration::from_secs(config.restart.window_seconds);
+        while incidents
+            .front()
+            .is_some_and(|incident| incident.elapsed() > window)
+        {
+            incidents.pop_front();
+        }
+
+        let restart = incidents.len() < config.restart.max_retries as usize;
+        incidents.push_back(Instant::now());
+
+        incident(
+            &supervisor,
+            pid,
+            &watchdog,
+            &format!("{host}:{port}"),
+            restart,
+        )
+        .await;
+
+        if !restart {
+            supervisor.line(
+                format!(
+                    "watchdog: server has hung {} times within {}s, giving up",
+                    incidents.len(),
+                    config.restart.window_seconds
+                )
+                .red()
+                .to_string(),
+            );
+            supervisor
+                .stop(supervisor.timeout)
+                .await
+                .unwrap_or_default();
+        }
+    }
+}
+
+async fn ping(host: &str, port: u16, timeout: Duration) -> bool {
+    let ping = tokio::task::spawn_blocking({
+        let host = host.to_string();
+
+        move || {
+            let mut server = msp::Conf::create_with_port(&host, port);
+            server.socket_conf.read_time_out = Some(timeout);
+            server.socket_conf.write_timeout = Some(timeout);
+
+            // a response msp cannot parse (e.g. one without a favicon) still is an answer
+            matches!(
+                server.get_server_status(),
+                Ok(_) | Err(msp::MspErr::DataErr(_))
+            )
+        }
+    });
+
+    // msp does not always honor its socket timeouts
+    matches!(tokio::time::timeout(timeout, ping).await, Ok(Ok(true)))
+}
+
+/// Captures a thread dump of the hung server, writes it to the incident log and
+/// restarts the server if `restart` is set.
+async fn incident(
+    supervisor: &Arc<Supervisor>,
+    pid: u32,
+    watchdog: &WatchdogConfig,
+    address: &str,
+    restart: bool,
+) {
+    supervisor.line(
+        "watchdog: server is not responding, capturing a thread dump ..."
+            .red()
+            .to_string(),
+    );
+
+    let dump = thread_dump(pid, supervisor.subscribe()).await;
+    let time = Local::now();
+
+    let mut content = vec![
+        format!("time: {}", time.format("%Y-%m-%d %H:%M:%S")),
+        format!("pid: {pid}"),
+        format!("address: {address}"),
+        format!(
+            "failed pings: {} (every {}s, {}s timeout)",
+            watchdog.max_failures, watchdog.interval_seconds, watchdog.timeout_seconds
+        ),
+        String::new(),
+    ];
+    if dump.is_empty() {
+        content.push("the server did not print a thread dump".to_string());
+    } else {
+        content.extend(dump);
+    }
+
+    let path = PathBuf::from(DIRECTORY).join(format!("{}.log", time.format("%Y-%m-%d_%H-%M-%S")));
+    let written = std::fs::create_dir_all(DIRECTORY)
+        .and_then(|_| std::fs::File::create(&path))
+        .and_then(|mut file| file.write_all((content.join("\n") + "\n").as_bytes()));
+
+    match written {
+        Ok(()) => supervisor.line(
+            format!("watchdog: incident logged to {}", path.display())
+                .red()
+                .to_string(),
+        ),
+        Err(err) => supervisor.line(
+            format!("watchdog: unable to log the incident ({err})")
+                .red()
+                .to_string(),
+        ),
+    }
+
+    if restart {
+        supervisor.line(
+            "watchdog: killing and restarting the server ..."
+                .red()
+                .to_string(),
+        );
+        supervisor.kill_and_restart().await;
+    }
+}
+
+/// Sends `SIGQUIT` to the server, which makes the JVM print a thread dump to stdout,
+/// and collects the printed lines until the output goes quiet.
+async fn thread_dump(pid: u32, mut events: broadcast::Receiver<Event>) -> Vec<String> {
+    let mut system = sysinfo::System::new();
+    let pid = sysinfo::Pid::from_u32(pid);
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+
+    let sent = system
+        .process(pid)
+        .and_then(|process| process.kill_with(Signal::Quit))
+        .unwrap_or(false);
+    if !sent {
+        return Vec::new();
+    }
+
+    let mut lines = Vec::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+
+    loop {
+        let quiet = tokio::time::Instant::now() + Duration::from_secs(2);
+
+        match tokio::time::timeout_at(quiet.min(deadline), events.recv()).await {
+            Ok(Ok(Event::Line { line })) => lines.push(line),
+            Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) => continue,
+            Ok(Err(broadcast::error::RecvError::Closed)) | Err(_) => break,
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,86 @@
+use chrono::Utc;
+use dirs::home_dir;
+use rand::{Rng, distr::Alphanumeric};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{io::Write, os::unix::fs::OpenOptionsExt, path::PathBuf, sync::LazyLock};
+
+pub static FILE: LazyLock<PathBuf> =
+    LazyLock::new(|| home_dir().unwrap().join(".mcvcli").join("tokens.json"));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Token {
+    pub name: String,
+    /// The sha256 of the token, the token itself is only shown once when it is created.
+    pub hash: String,
+    pub created: i64,
+}
+
+/// The bearer tokens accepted by `mcvcli serve`, stored in `~/.mcvcli/tokens.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Tokens {
+    pub tokens: Vec<Token>,
+}
+
+fn hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+impl Tokens {
+    pub fn load() -> Self {
+        std::fs::read_to_string(&*FILE)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        std::fs::create_dir_all(FILE.parent().unwrap())?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&*FILE)?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Token> {
+        self.tokens.iter().find(|token| token.name == name)
+    }
+
+    /// Creates a new token called `name`, returns the token.
+    pub fn create(&mut self, name: &str) -> String {
+        let token = format!(
+            "mcv_{}",
+            rand::rng()
+                .sample_iter(&Alphanumeric)
+                .take(40)
+                .map(char::from)
+                .collect::<String>()
+        );
+
+        self.tokens.push(Token {
+            name: name.to_string(),
+            hash: hash(&token),
+            created: Utc::now().timestamp(),
+        });
+
+        token
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.tokens.len();
+        self.tokens.retain(|token| token.name != name);
+
+        self.tokens.len() != len
+    }
+
+    pub fn verify(&self, token: &str) -> Option<&Token> {
+        let hash = hash(token);
+
+        self.tokens.iter().find(|entry| entry.hash == hash)
+    }
+}